@@ -17,6 +17,7 @@ import (
 	"investlog/internal/api"
 	"investlog/internal/config"
 	"investlog/internal/logging"
+	"investlog/internal/tlscert"
 	"investlog/pkg/investlog"
 )
 
@@ -34,12 +35,16 @@ func main() {
 	var host string
 	var webDir string
 	var debug bool
+	var tlsDir string
+	var backfillRates bool
 
 	flag.StringVar(&dataDir, "data-dir", "", "Directory for storing database and application data")
 	flag.IntVar(&port, "port", 8000, "Port to run the server on")
 	flag.StringVar(&host, "host", "127.0.0.1", "Host to bind the server to")
 	flag.StringVar(&webDir, "web-dir", "", "Directory for SPA static files (optional)")
 	flag.BoolVar(&debug, "debug", false, "Enable debug logging (overrides build mode)")
+	flag.StringVar(&tlsDir, "tls-dir", "", "Serve HTTPS with a self-signed certificate kept in this directory (optional)")
+	flag.BoolVar(&backfillRates, "backfill-exchange-rates", false, "Fetch missing daily exchange-rate history from the rate provider at startup")
 	flag.Parse()
 
 	if dataDir != "" {
@@ -95,12 +100,31 @@ func main() {
 		go watchParent(logger)
 	}
 
+	// Opt-in: catching up on daily exchange-rate history contacts the rate provider, which
+	// a local-first app must not do on every start. Otherwise it runs after the user fetches
+	// rates in Settings.
+	if backfillRates {
+		go func() {
+			inserted, failed, err := core.BackfillExchangeRateHistory()
+			if err != nil {
+				logger.Warn("exchange rate backfill failed", "err", err)
+				return
+			}
+			logger.Info("exchange rate backfill finished", "inserted", inserted, "errors", failed)
+		}()
+	}
+
 	addr := fmt.Sprintf("%s:%d", host, port)
 	handler := api.NewRouter(core)
 	if resolvedWebDir := resolveWebDir(webDir); resolvedWebDir != "" {
 		logger.Info("serving SPA", "web_dir", resolvedWebDir)
 		handler = api.WithSPA(handler, resolvedWebDir)
 	}
+	// Set by the desktop shell when it exposes the app on the local network.
+	lanPIN := os.Getenv("INVEST_LOG_LAN_PIN")
+	if lanPIN != "" {
+		handler = api.WithLANPairing(handler, lanPIN, tlsDir != "")
+	}
 	handler = middleware.Compress(5)(handler)
 
 	server := &http.Server{
@@ -112,8 +136,19 @@ func main() {
 		IdleTimeout:       60 * time.Second,
 	}
 
+	var certPath, keyPath string
+	if tlsDir != "" {
+		certPath, keyPath, err = tlscert.Ensure(tlsDir)
+		if err != nil {
+			logger.Error("failed to prepare tls certificate", "tls_dir", tlsDir, "err", err)
+			os.Exit(1)
+		}
+	}
+
 	logger.Info("server starting",
 		"addr", addr,
+		"tls", certPath != "",
+		"lan_pairing", lanPIN != "",
 		"host", host,
 		"port", port,
 		"pid", os.Getpid(),
@@ -121,7 +156,13 @@ func main() {
 		"db_path", dbPath,
 	)
 	go func() {
-		if err := server.ListenAndServe(); err != nil && err != http.ErrServerClosed {
+		var err error
+		if certPath != "" {
+			err = server.ListenAndServeTLS(certPath, keyPath)
+		} else {
+			err = server.ListenAndServe()
+		}
+		if err != nil && err != http.ErrServerClosed {
 			logger.Error("server error", "err", err)
 		}
 	}()
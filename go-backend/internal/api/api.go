@@ -123,6 +123,8 @@ func NewRouter(core *investlog.Core) http.Handler {
 	r.Get("/api/exchange-rates", h.getExchangeRates)
 	r.Put("/api/exchange-rates", h.setExchangeRate)
 	r.Post("/api/exchange-rates/refresh", h.refreshExchangeRates)
+	r.Get("/api/exchange-rates/history", h.getHistoricalExchangeRate)
+	r.Post("/api/exchange-rates/backfill", h.backfillExchangeRates)
 
 	// Symbols
 	r.Get("/api/symbols", h.getSymbols)
@@ -135,8 +137,14 @@ func NewRouter(core *investlog.Core) http.Handler {
 
 	// Storage
 	r.Get("/api/storage", h.getStorageInfo)
+	r.Get("/api/storage/stats", h.getStorageStats)
 	r.Post("/api/storage/switch", h.switchStorage)
 
+	// Diagnostics
+	r.Get("/api/self-test", h.runSelfTest)
+	r.Get("/api/log-level", h.getLogLevel)
+	r.Put("/api/log-level", h.setLogLevel)
+
 	return r
 }
 
@@ -868,6 +868,32 @@ func (h *handler) refreshExchangeRates(w http.ResponseWriter, r *http.Request) {
 	})
 }
 
+func (h *handler) getHistoricalExchangeRate(w http.ResponseWriter, r *http.Request) {
+	query := r.URL.Query()
+	toCurrency := query.Get("to")
+	if toCurrency == "" {
+		toCurrency = "CNY"
+	}
+	result, err := h.core.GetExchangeRateOn(query.Get("date"), query.Get("from"), toCurrency)
+	if err != nil {
+		writeError(w, http.StatusBadRequest, err.Error())
+		return
+	}
+	writeJSON(w, http.StatusOK, result)
+}
+
+func (h *handler) backfillExchangeRates(w http.ResponseWriter, r *http.Request) {
+	inserted, errors, err := h.core.BackfillExchangeRateHistory()
+	if err != nil {
+		writeError(w, http.StatusInternalServerError, err.Error())
+		return
+	}
+	writeJSON(w, http.StatusOK, map[string]any{
+		"inserted": inserted,
+		"errors":   errors,
+	})
+}
+
 func (h *handler) getSymbols(w http.ResponseWriter, r *http.Request) {
 	result, err := h.core.GetSymbols()
 	if err != nil {
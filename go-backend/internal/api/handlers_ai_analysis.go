@@ -134,6 +134,7 @@ func (h *handler) runAIAnalysisStream(w http.ResponseWriter, r *http.Request) {
 	result, err := h.core.RunAIAnalysisStream(investlog.RunAIAnalysisRequest{
 		MethodID:  payload.MethodID,
 		Variables: payload.Variables,
+		APIKey:    payload.APIKey,
 	}, func(delta string) error {
 		if delta == "" {
 			return nil
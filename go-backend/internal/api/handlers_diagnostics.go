@@ -0,0 +1,132 @@
+package api
+
+import (
+	"errors"
+	"fmt"
+	"net/http"
+	"os"
+	"path/filepath"
+
+	"investlog/internal/config"
+	"investlog/internal/logging"
+)
+
+// minFreeDiskBytes is the headroom below which writes (WAL growth, logs) start to be at risk.
+const minFreeDiskBytes = 200 * 1024 * 1024
+
+func (h *handler) runSelfTest(w http.ResponseWriter, r *http.Request) {
+	dbPath := ""
+	if h.core != nil {
+		dbPath = h.core.DBPath()
+	}
+	if dbPath == "" {
+		var err error
+		dbPath, err = config.GetDBPath()
+		if err != nil {
+			writeError(w, http.StatusInternalServerError, fmt.Errorf("resolve db path: %w", err).Error())
+			return
+		}
+	}
+	dataDir := filepath.Dir(dbPath)
+
+	checks := []selfTestCheck{
+		checkDataDirWritable(dataDir),
+		h.checkDatabase(),
+		checkDiskSpace(dataDir, minFreeDiskBytes),
+	}
+	resp := selfTestResponse{OK: true, DataDir: dataDir, DBPath: dbPath, Checks: checks}
+	for _, check := range checks {
+		if check.Status == selfTestFail {
+			resp.OK = false
+		}
+	}
+	writeJSON(w, http.StatusOK, resp)
+}
+
+func checkDataDirWritable(dir string) selfTestCheck {
+	check := selfTestCheck{Name: "data_dir_writable"}
+	file, err := os.CreateTemp(dir, ".selftest-*")
+	if err != nil {
+		check.Status = selfTestFail
+		check.Detail = err.Error()
+		return check
+	}
+	name := file.Name()
+	_ = file.Close()
+	if err := os.Remove(name); err != nil {
+		check.Status = selfTestFail
+		check.Detail = fmt.Sprintf("remove probe file: %v", err)
+		return check
+	}
+	check.Status = selfTestPass
+	check.Detail = dir
+	return check
+}
+
+func (h *handler) checkDatabase() selfTestCheck {
+	check := selfTestCheck{Name: "db_quick_check"}
+	if h.core == nil {
+		check.Status = selfTestFail
+		check.Detail = "database not open"
+		return check
+	}
+	result, err := h.core.QuickCheck()
+	switch {
+	case err != nil:
+		check.Status = selfTestFail
+		check.Detail = err.Error()
+	case result != "ok":
+		check.Status = selfTestFail
+		check.Detail = result
+	default:
+		check.Status = selfTestPass
+		check.Detail = result
+	}
+	return check
+}
+
+func checkDiskSpace(dir string, minFree uint64) selfTestCheck {
+	check := selfTestCheck{Name: "disk_space"}
+	free, err := diskFreeBytes(dir)
+	if errors.Is(err, errors.ErrUnsupported) {
+		check.Status = selfTestSkip
+		check.Detail = "not supported on this platform"
+		return check
+	}
+	if err != nil {
+		check.Status = selfTestFail
+		check.Detail = err.Error()
+		return check
+	}
+	check.FreeBytes = free
+	if free < minFree {
+		check.Status = selfTestFail
+		check.Detail = fmt.Sprintf("only %d MB free", free/(1024*1024))
+		return check
+	}
+	check.Status = selfTestPass
+	check.Detail = fmt.Sprintf("%d MB free", free/(1024*1024))
+	return check
+}
+
+func (h *handler) getLogLevel(w http.ResponseWriter, r *http.Request) {
+	writeJSON(w, http.StatusOK, logLevelResponse{Level: logging.Level().String()})
+}
+
+// setLogLevel lets the desktop shell turn on debug logging without restarting the backend.
+func (h *handler) setLogLevel(w http.ResponseWriter, r *http.Request) {
+	var payload logLevelPayload
+	if err := decodeJSON(r, &payload); err != nil {
+		writeError(w, http.StatusBadRequest, err.Error())
+		return
+	}
+	level, ok := logging.ParseLevel(payload.Level)
+	if !ok {
+		writeError(w, http.StatusBadRequest, fmt.Sprintf("unknown log level %q", payload.Level))
+		return
+	}
+	previous := logging.Level()
+	logging.SetLevel(level)
+	h.logger.Info("log level changed", "from", previous.String(), "to", level.String())
+	writeJSON(w, http.StatusOK, logLevelResponse{Level: level.String()})
+}
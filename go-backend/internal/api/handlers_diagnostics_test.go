@@ -0,0 +1,105 @@
+package api
+
+import (
+	"encoding/json"
+	"log/slog"
+	"net/http"
+	"path/filepath"
+	"testing"
+
+	"investlog/internal/logging"
+)
+
+func TestRunSelfTest(t *testing.T) {
+	router, cleanup, dataDir, dbName := setupStorageRouter(t)
+	defer cleanup()
+
+	rr := doRequest(router, http.MethodGet, "/api/self-test", nil)
+	if rr.Code != http.StatusOK {
+		t.Fatalf("GET /api/self-test: expected 200, got %d", rr.Code)
+	}
+	var resp selfTestResponse
+	if err := json.NewDecoder(rr.Body).Decode(&resp); err != nil {
+		t.Fatalf("decode response: %v", err)
+	}
+	if resp.DataDir != dataDir || filepath.Base(resp.DBPath) != dbName {
+		t.Fatalf("unexpected paths: %+v", resp)
+	}
+
+	statuses := map[string]string{}
+	for _, check := range resp.Checks {
+		statuses[check.Name] = check.Status
+	}
+	if statuses["data_dir_writable"] != selfTestPass {
+		t.Fatalf("expected data dir check to pass, got %+v", resp.Checks)
+	}
+	if statuses["db_quick_check"] != selfTestPass {
+		t.Fatalf("expected quick_check to pass, got %+v", resp.Checks)
+	}
+	if _, ok := statuses["disk_space"]; !ok {
+		t.Fatalf("expected disk_space check, got %+v", resp.Checks)
+	}
+}
+
+func TestSelfTestChecks(t *testing.T) {
+	tests := []struct {
+		name  string
+		check func() selfTestCheck
+		want  string
+	}{
+		{
+			name:  "missing data dir",
+			check: func() selfTestCheck { return checkDataDirWritable(filepath.Join(t.TempDir(), "missing")) },
+			want:  selfTestFail,
+		},
+		{
+			name:  "writable data dir",
+			check: func() selfTestCheck { return checkDataDirWritable(t.TempDir()) },
+			want:  selfTestPass,
+		},
+		{
+			name:  "no database",
+			check: (&handler{}).checkDatabase,
+			want:  selfTestFail,
+		},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			got := tt.check()
+			if got.Status != tt.want {
+				t.Fatalf("expected %s, got %+v", tt.want, got)
+			}
+		})
+	}
+}
+
+func TestLogLevel(t *testing.T) {
+	router, cleanup := setupTestRouter(t)
+	defer cleanup()
+	t.Cleanup(func() {
+		logging.SetLevel(slog.LevelInfo)
+	})
+
+	rr := doRequest(router, http.MethodPut, "/api/log-level", map[string]string{"level": "debug"})
+	if rr.Code != http.StatusOK {
+		t.Fatalf("PUT /api/log-level: expected 200, got %d", rr.Code)
+	}
+	if logging.Level() != slog.LevelDebug {
+		t.Fatalf("expected debug level, got %v", logging.Level())
+	}
+
+	rr = doRequest(router, http.MethodGet, "/api/log-level", nil)
+	var resp logLevelResponse
+	if err := json.NewDecoder(rr.Body).Decode(&resp); err != nil {
+		t.Fatalf("decode response: %v", err)
+	}
+	if resp.Level != "DEBUG" {
+		t.Fatalf("expected DEBUG, got %q", resp.Level)
+	}
+
+	rr = doRequest(router, http.MethodPut, "/api/log-level", map[string]string{"level": "verbose"})
+	if rr.Code != http.StatusBadRequest {
+		t.Fatalf("expected 400 for unknown level, got %d", rr.Code)
+	}
+}
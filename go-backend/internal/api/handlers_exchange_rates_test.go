@@ -41,6 +41,23 @@ func TestExchangeRatesEndpoints(t *testing.T) {
 		t.Fatalf("PUT /api/exchange-rates invalid payload: expected 400, got %d", rr.Code)
 	}
 
+	rr = doRequest(router, http.MethodGet, "/api/exchange-rates/history?date=2024-01-03&from=USD", nil)
+	if rr.Code != http.StatusOK {
+		t.Fatalf("GET /api/exchange-rates/history: expected 200, got %d, body: %s", rr.Code, rr.Body.String())
+	}
+	var historical map[string]any
+	if err := json.NewDecoder(rr.Body).Decode(&historical); err != nil {
+		t.Fatalf("decode historical rate response: %v", err)
+	}
+	if historical["to_currency"] != "CNY" || historical["rate"] == nil {
+		t.Fatalf("unexpected historical rate response: %v", historical)
+	}
+
+	rr = doRequest(router, http.MethodGet, "/api/exchange-rates/history?date=2024-13-01&from=USD", nil)
+	if rr.Code != http.StatusBadRequest {
+		t.Fatalf("GET /api/exchange-rates/history invalid date: expected 400, got %d", rr.Code)
+	}
+
 	rr = doRequest(router, http.MethodPost, "/api/exchange-rates/refresh", map[string]any{})
 	if rr.Code != http.StatusOK && rr.Code != http.StatusInternalServerError {
 		t.Fatalf("POST /api/exchange-rates/refresh: expected 200 or 500, got %d", rr.Code)
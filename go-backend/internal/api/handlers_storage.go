@@ -8,6 +8,7 @@ import (
 	"path/filepath"
 	"sort"
 	"strings"
+	"time"
 
 	"investlog/internal/config"
 	"investlog/pkg/investlog"
@@ -86,6 +87,81 @@ func (h *handler) getStorageInfo(w http.ResponseWriter, r *http.Request) {
 	})
 }
 
+func (h *handler) getStorageStats(w http.ResponseWriter, r *http.Request) {
+	dbPath := ""
+	if h.core != nil {
+		dbPath = h.core.DBPath()
+	}
+	if dbPath == "" {
+		var err error
+		dbPath, err = config.GetDBPath()
+		if err != nil {
+			writeError(w, http.StatusInternalServerError, fmt.Errorf("resolve db path: %w", err).Error())
+			return
+		}
+	}
+	dataDir := filepath.Dir(dbPath)
+	logsDir := filepath.Join(dataDir, "logs")
+
+	resp := storageStatsResponse{
+		DataDir: dataDir,
+		DB:      statStorageFile(dbPath),
+		WAL:     statStorageFile(dbPath + "-wal"),
+		SHM:     statStorageFile(dbPath + "-shm"),
+		LogsDir: logsDir,
+	}
+
+	dbFiles, err := listDBFiles(dataDir)
+	if err != nil && !errors.Is(err, os.ErrNotExist) {
+		writeError(w, http.StatusInternalServerError, fmt.Errorf("list storage files: %w", err).Error())
+		return
+	}
+	resp.DBFiles = len(dbFiles)
+
+	resp.LogFiles, resp.LogsSize, err = dirUsage(logsDir)
+	if err != nil && !errors.Is(err, os.ErrNotExist) {
+		writeError(w, http.StatusInternalServerError, fmt.Errorf("read logs dir: %w", err).Error())
+		return
+	}
+	resp.TotalSize = resp.DB.Size + resp.WAL.Size + resp.SHM.Size + resp.LogsSize
+
+	writeJSON(w, http.StatusOK, resp)
+}
+
+func statStorageFile(path string) storageFileStat {
+	stat := storageFileStat{Path: path}
+	info, err := os.Stat(path)
+	if err != nil || info.IsDir() {
+		return stat
+	}
+	stat.Exists = true
+	stat.Size = info.Size()
+	stat.ModifiedAt = info.ModTime().UTC().Format(time.RFC3339)
+	return stat
+}
+
+// dirUsage returns the number of regular files directly under dir and their total size.
+func dirUsage(dir string) (int, int64, error) {
+	entries, err := os.ReadDir(dir)
+	if err != nil {
+		return 0, 0, err
+	}
+	count := 0
+	var total int64
+	for _, entry := range entries {
+		if entry.IsDir() {
+			continue
+		}
+		info, err := entry.Info()
+		if err != nil {
+			continue
+		}
+		count++
+		total += info.Size()
+	}
+	return count, total, nil
+}
+
 func (h *handler) switchStorage(w http.ResponseWriter, r *http.Request) {
 	if strings.TrimSpace(os.Getenv("INVEST_LOG_DB_PATH")) != "" {
 		writeError(w, http.StatusBadRequest, "switching disabled when INVEST_LOG_DB_PATH is set")
@@ -163,6 +163,50 @@ func TestGetStorageInfoWithEnvPath(t *testing.T) {
 	}
 }
 
+func TestGetStorageStats(t *testing.T) {
+	router, cleanup, dataDir, dbName := setupStorageRouter(t)
+	defer cleanup()
+
+	logsDir := filepath.Join(dataDir, "logs")
+	if err := os.MkdirAll(logsDir, 0o755); err != nil {
+		t.Fatalf("mkdir logs: %v", err)
+	}
+	if err := os.WriteFile(filepath.Join(logsDir, "app-20260101.log"), []byte("hello"), 0o644); err != nil {
+		t.Fatalf("write log file: %v", err)
+	}
+
+	rr := doRequest(router, http.MethodGet, "/api/storage/stats", nil)
+	if rr.Code != http.StatusOK {
+		t.Fatalf("GET /api/storage/stats: expected 200, got %d", rr.Code)
+	}
+	var resp storageStatsResponse
+	if err := json.NewDecoder(rr.Body).Decode(&resp); err != nil {
+		t.Fatalf("decode response: %v", err)
+	}
+	if filepath.Base(resp.DB.Path) != dbName {
+		t.Fatalf("expected db path to end with %q, got %q", dbName, resp.DB.Path)
+	}
+	if !resp.DB.Exists || resp.DB.Size == 0 || resp.DB.ModifiedAt == "" {
+		t.Fatalf("expected populated db stat, got %+v", resp.DB)
+	}
+	if resp.DBFiles != 1 {
+		t.Fatalf("expected 1 db file, got %d", resp.DBFiles)
+	}
+	if resp.LogFiles != 1 || resp.LogsSize != 5 {
+		t.Fatalf("expected 1 log file of 5 bytes, got %d files / %d bytes", resp.LogFiles, resp.LogsSize)
+	}
+	if resp.TotalSize < resp.DB.Size+resp.LogsSize {
+		t.Fatalf("expected total size to include db and logs, got %d", resp.TotalSize)
+	}
+}
+
+func TestStatStorageFileMissing(t *testing.T) {
+	stat := statStorageFile(filepath.Join(t.TempDir(), "missing.db-wal"))
+	if stat.Exists || stat.Size != 0 || stat.ModifiedAt != "" {
+		t.Fatalf("expected empty stat for missing file, got %+v", stat)
+	}
+}
+
 func TestSwitchStorage(t *testing.T) {
 	router, cleanup, dataDir, dbName := setupStorageRouter(t)
 	defer cleanup()
@@ -0,0 +1,355 @@
+package api
+
+import (
+	"crypto/rand"
+	"crypto/subtle"
+	"encoding/hex"
+	"fmt"
+	"html"
+	"log/slog"
+	"net"
+	"net/http"
+	"strings"
+	"sync"
+	"time"
+)
+
+const (
+	lanSessionCookie = "investlog_lan"
+	lanPairPath      = "/lan/pair"
+	lanLogoutPath    = "/lan/logout"
+	lanTokenPath     = "/lan/token"
+	lanMaxFailures   = 5
+	lanLockout       = 5 * time.Minute
+	lanTokenTTL      = 5 * time.Minute
+	lanSessionTTL    = 12 * time.Hour
+)
+
+// lanReadOnlyAPI is everything a read-only (QR code) session may call: the GET routes the
+// overview, holdings, transactions and charts pages load. Anything else, including other
+// GETs, is refused.
+var lanReadOnlyAPI = map[string]bool{
+	"/api/health":                       true,
+	"/api/holdings":                     true,
+	"/api/holdings-by-currency":         true,
+	"/api/holdings-by-symbol":           true,
+	"/api/holdings-by-currency-account": true,
+	"/api/transactions":                 true,
+	"/api/portfolio-history":            true,
+	"/api/accounts":                     true,
+	"/api/asset-types":                  true,
+	"/api/allocation-settings":          true,
+	"/api/exchange-rates":               true,
+	"/api/exchange-rates/history":       true,
+	"/api/symbols":                      true,
+}
+
+// lanLoopbackOnlyAPI lists API prefixes that never leave the Mac, even for paired devices:
+// they return the stored AI key, absolute paths on disk, or the operation log, or they
+// change backend state (self-test writes a probe file into the data dir).
+var lanLoopbackOnlyAPI = []string{
+	"/api/ai-settings",
+	"/api/storage",
+	"/api/operation-logs",
+	"/api/self-test",
+	"/api/log-level",
+}
+
+// WithLANPairing guards requests that don't come from loopback: a browser on the local
+// network must first enter pin on a pairing page, which sets a session cookie. Loopback
+// clients (the desktop shell) pass straight through. Sessions live in memory for
+// lanSessionTTL (POST /lan/logout ends one early), so every backend restart requires
+// pairing again. Wrong PINs lock out the sending address, not every device, and paired
+// devices still can't reach the routes in lanLoopbackOnlyAPI.
+//
+// The shell can also mint a single-use pairing token (POST /lan/token from loopback) for a
+// QR code; opening /lan/pair?token=... pairs that browser as a read-only session, which may
+// only make the GET requests in lanReadOnlyAPI.
+func WithLANPairing(next http.Handler, pin string, secure bool) http.Handler {
+	return &lanPairing{
+		next:     next,
+		pin:      pin,
+		secure:   secure,
+		sessions: map[string]lanSession{},
+		tokens:   map[string]time.Time{},
+		failures: map[string]lanFailures{},
+		now:      time.Now,
+	}
+}
+
+type lanSession struct {
+	readOnly bool
+	expires  time.Time
+}
+
+// lanFailures counts wrong PINs from one remote address; the count starts over once the
+// address has been quiet for lanLockout.
+type lanFailures struct {
+	count       int
+	last        time.Time
+	lockedUntil time.Time
+}
+
+type lanPairing struct {
+	next   http.Handler
+	pin    string
+	secure bool
+	now    func() time.Time
+
+	mu       sync.Mutex
+	sessions map[string]lanSession
+	tokens   map[string]time.Time
+	failures map[string]lanFailures
+}
+
+func (p *lanPairing) ServeHTTP(w http.ResponseWriter, r *http.Request) {
+	if isLoopbackRemote(r.RemoteAddr) {
+		if r.URL.Path == lanTokenPath && r.Method == http.MethodPost {
+			p.issueToken(w)
+			return
+		}
+		p.next.ServeHTTP(w, r)
+		return
+	}
+	if r.URL.Path == lanLogoutPath && r.Method == http.MethodPost {
+		p.logout(w, r)
+		return
+	}
+	if session, ok := p.session(r); ok {
+		if isLoopbackOnlyAPI(r.URL.Path) {
+			writeError(w, http.StatusForbidden, "not available over the local network")
+			return
+		}
+		if session.readOnly && !readOnlyAllowed(r) {
+			writeError(w, http.StatusForbidden, "read-only session")
+			return
+		}
+		p.next.ServeHTTP(w, r)
+		return
+	}
+	if r.URL.Path == lanPairPath && r.Method == http.MethodPost {
+		p.pair(w, r)
+		return
+	}
+	if r.URL.Path == lanPairPath && r.Method == http.MethodGet && r.URL.Query().Get("token") != "" {
+		p.pairWithToken(w, r)
+		return
+	}
+	if strings.HasPrefix(r.URL.Path, "/api/") {
+		writeError(w, http.StatusUnauthorized, "pairing required")
+		return
+	}
+	writeLANPairPage(w, http.StatusUnauthorized, "")
+}
+
+func (p *lanPairing) session(r *http.Request) (lanSession, bool) {
+	cookie, err := r.Cookie(lanSessionCookie)
+	if err != nil || cookie.Value == "" {
+		return lanSession{}, false
+	}
+	p.mu.Lock()
+	defer p.mu.Unlock()
+	session, ok := p.sessions[cookie.Value]
+	if ok && !p.now().Before(session.expires) {
+		delete(p.sessions, cookie.Value)
+		return lanSession{}, false
+	}
+	return session, ok
+}
+
+func (p *lanPairing) logout(w http.ResponseWriter, r *http.Request) {
+	if cookie, err := r.Cookie(lanSessionCookie); err == nil {
+		p.mu.Lock()
+		delete(p.sessions, cookie.Value)
+		p.mu.Unlock()
+	}
+	http.SetCookie(w, &http.Cookie{
+		Name:     lanSessionCookie,
+		Value:    "",
+		Path:     "/",
+		MaxAge:   -1,
+		HttpOnly: true,
+		Secure:   p.secure,
+		SameSite: http.SameSiteStrictMode,
+	})
+	http.Redirect(w, r, "/", http.StatusSeeOther)
+}
+
+func (p *lanPairing) pair(w http.ResponseWriter, r *http.Request) {
+	r.Body = http.MaxBytesReader(w, r.Body, 1024)
+	entered := strings.TrimSpace(r.PostFormValue("pin"))
+
+	remote := remoteHost(r.RemoteAddr)
+	p.mu.Lock()
+	defer p.mu.Unlock()
+	now := p.now()
+	for addr, f := range p.failures {
+		if now.Sub(f.last) >= lanLockout && !now.Before(f.lockedUntil) {
+			delete(p.failures, addr)
+		}
+	}
+	f := p.failures[remote]
+	if now.Before(f.lockedUntil) {
+		writeLANPairPage(w, http.StatusTooManyRequests, "Too many attempts. Try again in a few minutes.")
+		return
+	}
+	if entered == "" || subtle.ConstantTimeCompare([]byte(entered), []byte(p.pin)) != 1 {
+		f.count++
+		f.last = now
+		slog.Default().Warn("lan pairing failed", "remote", r.RemoteAddr, "failures", f.count)
+		if f.count >= lanMaxFailures {
+			f.count = 0
+			f.lockedUntil = now.Add(lanLockout)
+		}
+		p.failures[remote] = f
+		writeLANPairPage(w, http.StatusUnauthorized, "Incorrect PIN.")
+		return
+	}
+
+	delete(p.failures, remote)
+	p.startSession(w, r, lanSession{})
+}
+
+// issueToken is only reachable from loopback. Expired tokens are dropped here, so the map
+// stays as small as the number of QR codes shown in the last few minutes.
+func (p *lanPairing) issueToken(w http.ResponseWriter) {
+	token, err := newLANSessionToken()
+	if err != nil {
+		writeError(w, http.StatusInternalServerError, err.Error())
+		return
+	}
+	p.mu.Lock()
+	now := p.now()
+	for existing, expires := range p.tokens {
+		if !now.Before(expires) {
+			delete(p.tokens, existing)
+		}
+	}
+	p.tokens[token] = now.Add(lanTokenTTL)
+	p.mu.Unlock()
+	writeJSON(w, http.StatusOK, map[string]any{
+		"token":      token,
+		"path":       lanPairPath + "?token=" + token,
+		"expires_in": int(lanTokenTTL.Seconds()),
+	})
+}
+
+// pairWithToken consumes the token whether or not it is still valid, so a scanned code
+// can't be reused.
+func (p *lanPairing) pairWithToken(w http.ResponseWriter, r *http.Request) {
+	token := r.URL.Query().Get("token")
+
+	p.mu.Lock()
+	defer p.mu.Unlock()
+	expires, ok := p.tokens[token]
+	delete(p.tokens, token)
+	if !ok || !p.now().Before(expires) {
+		slog.Default().Warn("lan pairing token rejected", "remote", r.RemoteAddr)
+		writeLANPairPage(w, http.StatusUnauthorized, "This pairing code has expired. Show a new one on your Mac or enter the PIN.")
+		return
+	}
+	p.startSession(w, r, lanSession{readOnly: true})
+}
+
+// startSession must be called with p.mu held. Expired sessions are dropped here, so the
+// map only holds devices paired within the last lanSessionTTL.
+func (p *lanPairing) startSession(w http.ResponseWriter, r *http.Request, session lanSession) {
+	token, err := newLANSessionToken()
+	if err != nil {
+		writeError(w, http.StatusInternalServerError, err.Error())
+		return
+	}
+	now := p.now()
+	for existing, s := range p.sessions {
+		if !now.Before(s.expires) {
+			delete(p.sessions, existing)
+		}
+	}
+	session.expires = now.Add(lanSessionTTL)
+	p.sessions[token] = session
+	slog.Default().Info("lan device paired", "remote", r.RemoteAddr, "read_only", session.readOnly)
+	http.SetCookie(w, &http.Cookie{
+		Name:     lanSessionCookie,
+		Value:    token,
+		Path:     "/",
+		MaxAge:   int(lanSessionTTL.Seconds()),
+		HttpOnly: true,
+		Secure:   p.secure,
+		SameSite: http.SameSiteStrictMode,
+	})
+	http.Redirect(w, r, "/", http.StatusSeeOther)
+}
+
+// readOnlyAllowed lets read-only sessions load the app's static files and the listed API
+// reads, and nothing that changes state.
+func readOnlyAllowed(r *http.Request) bool {
+	if r.Method != http.MethodGet && r.Method != http.MethodHead {
+		return false
+	}
+	if !strings.HasPrefix(r.URL.Path, "/api/") {
+		return true
+	}
+	return lanReadOnlyAPI[r.URL.Path]
+}
+
+func isLoopbackOnlyAPI(path string) bool {
+	for _, prefix := range lanLoopbackOnlyAPI {
+		if path == prefix || strings.HasPrefix(path, prefix+"/") {
+			return true
+		}
+	}
+	return false
+}
+
+func remoteHost(remoteAddr string) string {
+	host, _, err := net.SplitHostPort(remoteAddr)
+	if err != nil {
+		return remoteAddr
+	}
+	return host
+}
+
+func isLoopbackRemote(remoteAddr string) bool {
+	ip := net.ParseIP(remoteHost(remoteAddr))
+	return ip != nil && ip.IsLoopback()
+}
+
+func newLANSessionToken() (string, error) {
+	buf := make([]byte, 32)
+	if _, err := rand.Read(buf); err != nil {
+		return "", fmt.Errorf("generate session token: %w", err)
+	}
+	return hex.EncodeToString(buf), nil
+}
+
+func writeLANPairPage(w http.ResponseWriter, status int, message string) {
+	w.Header().Set("Content-Type", "text/html; charset=utf-8")
+	w.Header().Set("Cache-Control", "no-store")
+	w.Header().Set("Content-Security-Policy", "default-src 'none'; style-src 'unsafe-inline'; form-action 'self'")
+	w.WriteHeader(status)
+	_, _ = fmt.Fprintf(w, lanPairPage, lanPairPath, html.EscapeString(message))
+}
+
+const lanPairPage = `<!doctype html>
+<html>
+<head>
+<meta charset="utf-8">
+<meta name="viewport" content="width=device-width, initial-scale=1">
+<title>Invest Log</title>
+<style>
+body { font-family: -apple-system, system-ui, sans-serif; max-width: 22rem; margin: 15vh auto; padding: 0 1rem; }
+input, button { font-size: 1.25rem; padding: 0.5rem; width: 100%%; box-sizing: border-box; margin-top: 0.75rem; }
+p.error { color: #c0392b; }
+</style>
+</head>
+<body>
+<h1>Invest Log</h1>
+<p>Enter the PIN shown on your Mac under Invest Log &gt; Allow Access from Local Network.</p>
+<form method="post" action="%s">
+<input name="pin" inputmode="numeric" autocomplete="one-time-code" autofocus required>
+<button type="submit">Pair</button>
+</form>
+<p class="error">%s</p>
+</body>
+</html>
+`
@@ -0,0 +1,270 @@
+package api
+
+import (
+	"encoding/json"
+	"net/http"
+	"net/http/httptest"
+	"net/url"
+	"strings"
+	"testing"
+	"time"
+)
+
+func newLANPairingTestHandler() *lanPairing {
+	next := http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		_, _ = w.Write([]byte("OK"))
+	})
+	return WithLANPairing(next, "123456", true).(*lanPairing)
+}
+
+func postLANPin(h http.Handler, pin string) *httptest.ResponseRecorder {
+	return postLANPinFrom(h, "192.0.2.1:1234", pin)
+}
+
+func postLANPinFrom(h http.Handler, remoteAddr, pin string) *httptest.ResponseRecorder {
+	form := url.Values{"pin": {pin}}
+	req := httptest.NewRequest(http.MethodPost, lanPairPath, strings.NewReader(form.Encode()))
+	req.Header.Set("Content-Type", "application/x-www-form-urlencoded")
+	req.RemoteAddr = remoteAddr
+	rr := httptest.NewRecorder()
+	h.ServeHTTP(rr, req)
+	return rr
+}
+
+func TestWithLANPairing_GuardsRemoteRequests(t *testing.T) {
+	h := newLANPairingTestHandler()
+
+	tests := []struct {
+		name       string
+		remoteAddr string
+		path       string
+		wantStatus int
+		wantBody   string
+	}{
+		{name: "loopback ipv4", remoteAddr: "127.0.0.1:50000", path: "/api/health", wantStatus: http.StatusOK, wantBody: "OK"},
+		{name: "loopback ipv6", remoteAddr: "[::1]:50000", path: "/", wantStatus: http.StatusOK, wantBody: "OK"},
+		{name: "remote api", remoteAddr: "192.168.1.20:50000", path: "/api/holdings", wantStatus: http.StatusUnauthorized, wantBody: "pairing required"},
+		{name: "remote page", remoteAddr: "192.168.1.20:50000", path: "/", wantStatus: http.StatusUnauthorized, wantBody: `name="pin"`},
+	}
+
+	for _, tc := range tests {
+		t.Run(tc.name, func(t *testing.T) {
+			req := httptest.NewRequest(http.MethodGet, tc.path, nil)
+			req.RemoteAddr = tc.remoteAddr
+			rr := httptest.NewRecorder()
+			h.ServeHTTP(rr, req)
+			if rr.Code != tc.wantStatus {
+				t.Fatalf("expected %d, got %d", tc.wantStatus, rr.Code)
+			}
+			if !strings.Contains(rr.Body.String(), tc.wantBody) {
+				t.Fatalf("expected body containing %q, got %q", tc.wantBody, rr.Body.String())
+			}
+		})
+	}
+}
+
+func TestWithLANPairing_PairsWithPIN(t *testing.T) {
+	h := newLANPairingTestHandler()
+
+	rr := postLANPin(h, "000000")
+	if rr.Code != http.StatusUnauthorized {
+		t.Fatalf("wrong pin: expected 401, got %d", rr.Code)
+	}
+
+	rr = postLANPin(h, "123456")
+	if rr.Code != http.StatusSeeOther {
+		t.Fatalf("correct pin: expected 303, got %d", rr.Code)
+	}
+	cookies := rr.Result().Cookies()
+	if len(cookies) != 1 || cookies[0].Name != lanSessionCookie || !cookies[0].HttpOnly || !cookies[0].Secure {
+		t.Fatalf("expected secure http-only session cookie, got %+v", cookies)
+	}
+
+	req := httptest.NewRequest(http.MethodGet, "/api/holdings", nil)
+	req.AddCookie(cookies[0])
+	rr = httptest.NewRecorder()
+	h.ServeHTTP(rr, req)
+	if rr.Code != http.StatusOK {
+		t.Fatalf("paired request: expected 200, got %d", rr.Code)
+	}
+
+	req = httptest.NewRequest(http.MethodGet, "/api/holdings", nil)
+	req.AddCookie(&http.Cookie{Name: lanSessionCookie, Value: "forged"})
+	rr = httptest.NewRecorder()
+	h.ServeHTTP(rr, req)
+	if rr.Code != http.StatusUnauthorized {
+		t.Fatalf("unknown session: expected 401, got %d", rr.Code)
+	}
+}
+
+func TestWithLANPairing_LocksOutAfterFailures(t *testing.T) {
+	h := newLANPairingTestHandler()
+	now := time.Date(2024, 1, 2, 10, 0, 0, 0, time.UTC)
+	h.now = func() time.Time { return now }
+
+	for i := 0; i < lanMaxFailures; i++ {
+		if rr := postLANPin(h, "000000"); rr.Code != http.StatusUnauthorized {
+			t.Fatalf("attempt %d: expected 401, got %d", i+1, rr.Code)
+		}
+	}
+	if rr := postLANPin(h, "123456"); rr.Code != http.StatusTooManyRequests {
+		t.Fatalf("locked out: expected 429, got %d", rr.Code)
+	}
+
+	now = now.Add(lanLockout)
+	if rr := postLANPin(h, "123456"); rr.Code != http.StatusSeeOther {
+		t.Fatalf("after lockout: expected 303, got %d", rr.Code)
+	}
+}
+
+func TestWithLANPairing_LockoutIsPerAddress(t *testing.T) {
+	h := newLANPairingTestHandler()
+	now := time.Date(2024, 1, 2, 10, 0, 0, 0, time.UTC)
+	h.now = func() time.Time { return now }
+
+	for i := 0; i < lanMaxFailures; i++ {
+		postLANPinFrom(h, "192.168.1.66:40000", "000000")
+	}
+	if rr := postLANPinFrom(h, "192.168.1.66:40001", "123456"); rr.Code != http.StatusTooManyRequests {
+		t.Fatalf("guessing address: expected 429, got %d", rr.Code)
+	}
+	if rr := postLANPinFrom(h, "192.168.1.20:50000", "123456"); rr.Code != http.StatusSeeOther {
+		t.Fatalf("other address: expected 303, got %d", rr.Code)
+	}
+}
+
+func TestWithLANPairing_SessionLimits(t *testing.T) {
+	h := newLANPairingTestHandler()
+	now := time.Date(2024, 1, 2, 10, 0, 0, 0, time.UTC)
+	h.now = func() time.Time { return now }
+
+	pairCookie := func() *http.Cookie {
+		rr := postLANPin(h, "123456")
+		cookies := rr.Result().Cookies()
+		if rr.Code != http.StatusSeeOther || len(cookies) != 1 {
+			t.Fatalf("pairing: expected 303 with cookie, got %d %+v", rr.Code, cookies)
+		}
+		return cookies[0]
+	}
+	get := func(path string, cookie *http.Cookie) int {
+		req := httptest.NewRequest(http.MethodGet, path, nil)
+		req.AddCookie(cookie)
+		rr := httptest.NewRecorder()
+		h.ServeHTTP(rr, req)
+		return rr.Code
+	}
+
+	cookie := pairCookie()
+	tests := []struct {
+		path       string
+		wantStatus int
+	}{
+		{path: "/api/holdings", wantStatus: http.StatusOK},
+		{path: "/api/ai-settings", wantStatus: http.StatusForbidden},
+		{path: "/api/storage", wantStatus: http.StatusForbidden},
+		{path: "/api/storage/stats", wantStatus: http.StatusForbidden},
+		{path: "/api/operation-logs", wantStatus: http.StatusForbidden},
+		{path: "/api/self-test", wantStatus: http.StatusForbidden},
+	}
+	for _, tc := range tests {
+		if got := get(tc.path, cookie); got != tc.wantStatus {
+			t.Fatalf("GET %s: expected %d, got %d", tc.path, tc.wantStatus, got)
+		}
+	}
+
+	req := httptest.NewRequest(http.MethodPost, lanLogoutPath, nil)
+	req.AddCookie(cookie)
+	rr := httptest.NewRecorder()
+	h.ServeHTTP(rr, req)
+	if rr.Code != http.StatusSeeOther {
+		t.Fatalf("logout: expected 303, got %d", rr.Code)
+	}
+	if got := get("/api/holdings", cookie); got != http.StatusUnauthorized {
+		t.Fatalf("after logout: expected 401, got %d", got)
+	}
+
+	cookie = pairCookie()
+	now = now.Add(lanSessionTTL)
+	if got := get("/api/holdings", cookie); got != http.StatusUnauthorized {
+		t.Fatalf("expired session: expected 401, got %d", got)
+	}
+}
+
+func TestWithLANPairing_TokenPairsReadOnlySession(t *testing.T) {
+	h := newLANPairingTestHandler()
+	now := time.Date(2024, 1, 2, 10, 0, 0, 0, time.UTC)
+	h.now = func() time.Time { return now }
+
+	req := httptest.NewRequest(http.MethodPost, lanTokenPath, nil)
+	req.RemoteAddr = "192.168.1.20:50000"
+	rr := httptest.NewRecorder()
+	h.ServeHTTP(rr, req)
+	if rr.Code != http.StatusUnauthorized {
+		t.Fatalf("remote token request: expected 401, got %d", rr.Code)
+	}
+
+	issue := func() string {
+		req := httptest.NewRequest(http.MethodPost, lanTokenPath, nil)
+		req.RemoteAddr = "127.0.0.1:50000"
+		rr := httptest.NewRecorder()
+		h.ServeHTTP(rr, req)
+		if rr.Code != http.StatusOK {
+			t.Fatalf("issue token: expected 200, got %d", rr.Code)
+		}
+		var body struct {
+			Path string `json:"path"`
+		}
+		if err := json.Unmarshal(rr.Body.Bytes(), &body); err != nil {
+			t.Fatalf("decode token response: %v", err)
+		}
+		return body.Path
+	}
+	open := func(path string) *httptest.ResponseRecorder {
+		req := httptest.NewRequest(http.MethodGet, path, nil)
+		rr := httptest.NewRecorder()
+		h.ServeHTTP(rr, req)
+		return rr
+	}
+
+	path := issue()
+	rr = open(path)
+	if rr.Code != http.StatusSeeOther {
+		t.Fatalf("token pairing: expected 303, got %d", rr.Code)
+	}
+	cookies := rr.Result().Cookies()
+	if len(cookies) != 1 {
+		t.Fatalf("expected session cookie, got %+v", cookies)
+	}
+	if rr = open(path); rr.Code != http.StatusUnauthorized {
+		t.Fatalf("reused token: expected 401, got %d", rr.Code)
+	}
+
+	tests := []struct {
+		method     string
+		path       string
+		wantStatus int
+	}{
+		{method: http.MethodGet, path: "/api/transactions", wantStatus: http.StatusOK},
+		{method: http.MethodGet, path: "/", wantStatus: http.StatusOK},
+		{method: http.MethodPost, path: "/api/transactions", wantStatus: http.StatusForbidden},
+		{method: http.MethodDelete, path: "/api/transactions/1", wantStatus: http.StatusForbidden},
+		{method: http.MethodGet, path: "/api/self-test", wantStatus: http.StatusForbidden},
+		{method: http.MethodGet, path: "/api/ai-settings", wantStatus: http.StatusForbidden},
+		{method: http.MethodGet, path: "/api/ai/holdings-analysis/history", wantStatus: http.StatusForbidden},
+	}
+	for _, tc := range tests {
+		req := httptest.NewRequest(tc.method, tc.path, nil)
+		req.AddCookie(cookies[0])
+		rr := httptest.NewRecorder()
+		h.ServeHTTP(rr, req)
+		if rr.Code != tc.wantStatus {
+			t.Fatalf("%s %s with read-only session: expected %d, got %d", tc.method, tc.path, tc.wantStatus, rr.Code)
+		}
+	}
+
+	path = issue()
+	now = now.Add(lanTokenTTL)
+	if rr = open(path); rr.Code != http.StatusUnauthorized {
+		t.Fatalf("expired token: expected 401, got %d", rr.Code)
+	}
+}
@@ -83,6 +83,7 @@ type aiAnalysisMethodPayload struct {
 type aiAnalysisStreamPayload struct {
 	MethodID  int64             `json:"method_id"`
 	Variables map[string]string `json:"variables"`
+	APIKey    string            `json:"api_key"`
 }
 
 type aiSymbolAnalysisPayload struct {
@@ -172,6 +173,53 @@ type storageSwitchPayload struct {
 	Create bool   `json:"create"`
 }
 
+type storageFileStat struct {
+	Path       string `json:"path"`
+	Exists     bool   `json:"exists"`
+	Size       int64  `json:"size"`
+	ModifiedAt string `json:"modified_at,omitempty"`
+}
+
+type storageStatsResponse struct {
+	DataDir   string          `json:"data_dir"`
+	DB        storageFileStat `json:"db"`
+	WAL       storageFileStat `json:"wal"`
+	SHM       storageFileStat `json:"shm"`
+	DBFiles   int             `json:"db_files"`
+	LogsDir   string          `json:"logs_dir"`
+	LogFiles  int             `json:"log_files"`
+	LogsSize  int64           `json:"logs_size"`
+	TotalSize int64           `json:"total_size"`
+}
+
+const (
+	selfTestPass = "pass"
+	selfTestFail = "fail"
+	selfTestSkip = "skip"
+)
+
+type selfTestCheck struct {
+	Name      string `json:"name"`
+	Status    string `json:"status"`
+	Detail    string `json:"detail,omitempty"`
+	FreeBytes uint64 `json:"free_bytes,omitempty"`
+}
+
+type selfTestResponse struct {
+	OK      bool            `json:"ok"`
+	DataDir string          `json:"data_dir"`
+	DBPath  string          `json:"db_path"`
+	Checks  []selfTestCheck `json:"checks"`
+}
+
+type logLevelPayload struct {
+	Level string `json:"level"`
+}
+
+type logLevelResponse struct {
+	Level string `json:"level"`
+}
+
 type storageInfoResponse struct {
 	DBName       string   `json:"db_name"`
 	DBPath       string   `json:"db_path"`
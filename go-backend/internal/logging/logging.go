@@ -19,6 +19,9 @@ const (
 	envLogFormat = "INVEST_LOG_LOG_FORMAT"
 )
 
+// level is shared by the loggers NewLogger builds, so it can be changed while running.
+var level = new(slog.LevelVar)
+
 // DailyWriter writes logs into a date-based file and prunes old files.
 type DailyWriter struct {
 	dir           string
@@ -125,44 +128,57 @@ func (w *DailyWriter) cleanup(now time.Time) {
 }
 
 // NewLogger creates a slog.Logger writing to stdout and a daily file.
-func NewLogger(logDir string, level slog.Level) (*slog.Logger, *DailyWriter, error) {
+func NewLogger(logDir string, fallback slog.Level) (*slog.Logger, *DailyWriter, error) {
 	writer, err := NewDailyWriter(logDir, 7)
 	if err != nil {
 		return nil, nil, err
 	}
 	multi := io.MultiWriter(os.Stdout, writer)
-	effectiveLevel := resolveLevel(level)
-	handler := newHandler(multi, effectiveLevel)
+	level.Set(resolveLevel(fallback))
+	handler := newHandler(multi, level)
 	logger := slog.New(handler).With("service", defaultPrefix)
 	slog.SetDefault(logger)
 	return logger, writer, nil
 }
 
-func resolveLevel(fallback slog.Level) slog.Level {
-	value := strings.TrimSpace(os.Getenv(envLogLevel))
-	if value == "" {
-		return fallback
-	}
+// SetLevel changes the minimum level of loggers created by NewLogger.
+func SetLevel(l slog.Level) {
+	level.Set(l)
+}
+
+// Level returns the current minimum level.
+func Level() slog.Level {
+	return level.Level()
+}
 
-	switch strings.ToLower(value) {
+// ParseLevel accepts debug, info, warn(ing), error or a numeric slog level.
+func ParseLevel(value string) (slog.Level, bool) {
+	switch strings.ToLower(strings.TrimSpace(value)) {
 	case "debug":
-		return slog.LevelDebug
+		return slog.LevelDebug, true
 	case "info":
-		return slog.LevelInfo
+		return slog.LevelInfo, true
 	case "warn", "warning":
-		return slog.LevelWarn
+		return slog.LevelWarn, true
 	case "error":
-		return slog.LevelError
+		return slog.LevelError, true
 	default:
-		if i, err := strconv.Atoi(value); err == nil {
-			return slog.Level(i)
+		if i, err := strconv.Atoi(strings.TrimSpace(value)); err == nil {
+			return slog.Level(i), true
 		}
-		return fallback
+		return 0, false
+	}
+}
+
+func resolveLevel(fallback slog.Level) slog.Level {
+	if parsed, ok := ParseLevel(os.Getenv(envLogLevel)); ok {
+		return parsed
 	}
+	return fallback
 }
 
-func newHandler(w io.Writer, level slog.Level) slog.Handler {
-	options := &slog.HandlerOptions{Level: level}
+func newHandler(w io.Writer, level slog.Leveler) slog.Handler {
+	options := &slog.HandlerOptions{Level: level, ReplaceAttr: redactAttr}
 	format := strings.ToLower(strings.TrimSpace(os.Getenv(envLogFormat)))
 	if format == "json" {
 		return slog.NewJSONHandler(w, options)
@@ -162,3 +162,110 @@ func TestNewLoggerSetsSlogDefault(t *testing.T) {
 		t.Fatalf("expected slog.Default to be updated")
 	}
 }
+
+func TestParseLevel(t *testing.T) {
+	tests := []struct {
+		input  string
+		want   slog.Level
+		wantOK bool
+	}{
+		{input: "debug", want: slog.LevelDebug, wantOK: true},
+		{input: " INFO ", want: slog.LevelInfo, wantOK: true},
+		{input: "warning", want: slog.LevelWarn, wantOK: true},
+		{input: "error", want: slog.LevelError, wantOK: true},
+		{input: "2", want: slog.Level(2), wantOK: true},
+		{input: "", wantOK: false},
+		{input: "verbose", wantOK: false},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.input, func(t *testing.T) {
+			got, ok := ParseLevel(tt.input)
+			if ok != tt.wantOK {
+				t.Fatalf("ParseLevel(%q) ok = %v, want %v", tt.input, ok, tt.wantOK)
+			}
+			if ok && got != tt.want {
+				t.Fatalf("ParseLevel(%q) = %v, want %v", tt.input, got, tt.want)
+			}
+		})
+	}
+}
+
+func TestSetLevelAffectsExistingLogger(t *testing.T) {
+	dir := t.TempDir()
+	logger, writer, err := NewLogger(dir, slog.LevelInfo)
+	if err != nil {
+		t.Fatalf("NewLogger: %v", err)
+	}
+	t.Cleanup(func() {
+		_ = writer.Close()
+		SetLevel(slog.LevelInfo)
+	})
+
+	if logger.Enabled(nil, slog.LevelDebug) {
+		t.Fatalf("expected debug to be disabled initially")
+	}
+	SetLevel(slog.LevelDebug)
+	if !logger.Enabled(nil, slog.LevelDebug) {
+		t.Fatalf("expected debug to be enabled after SetLevel")
+	}
+	if Level() != slog.LevelDebug {
+		t.Fatalf("expected Level to report debug, got %v", Level())
+	}
+}
+
+func TestRedact(t *testing.T) {
+	home, err := os.UserHomeDir()
+	if err != nil || len(home) <= 1 {
+		t.Skip("no usable home directory")
+	}
+
+	tests := []struct {
+		name  string
+		input string
+		want  string
+	}{
+		{name: "home path", input: "open " + filepath.Join(home, "data", "a.db"), want: "open " + filepath.Join("~", "data", "a.db")},
+		{name: "bearer token", input: "Authorization: Bearer abcdefghijkl", want: "Authorization: Bearer [REDACTED]"},
+		{name: "openai key", input: "key sk-abcdefghijklmnop used", want: "key sk-[REDACTED] used"},
+		{name: "json secret", input: `{"api_key":"secret-value","model":"x"}`, want: `{"api_key":"[REDACTED]","model":"x"}`},
+		{name: "query token", input: "url?token=abc123&x=1", want: "url?token=[REDACTED]&x=1"},
+		{name: "account path", input: "/api/accounts/my-broker", want: "/api/accounts/[REDACTED]"},
+		{name: "plain", input: "holdings refreshed", want: "holdings refreshed"},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			if got := Redact(tt.input); got != tt.want {
+				t.Fatalf("Redact(%q) = %q, want %q", tt.input, got, tt.want)
+			}
+		})
+	}
+}
+
+func TestLoggerRedactsAttributes(t *testing.T) {
+	dir := t.TempDir()
+	logger, writer, err := NewLogger(dir, slog.LevelInfo)
+	if err != nil {
+		t.Fatalf("NewLogger: %v", err)
+	}
+	t.Cleanup(func() {
+		_ = writer.Close()
+	})
+
+	logger.Info("saving settings", "api_key", "sk-live-123456789", "account_id", "my-broker")
+	_ = writer.Close()
+
+	date := time.Now().Format("20060102")
+	data, err := os.ReadFile(filepath.Join(dir, defaultPrefix+"-"+date+".log"))
+	if err != nil {
+		t.Fatalf("read log: %v", err)
+	}
+	content := string(data)
+	if strings.Contains(content, "sk-live-123456789") || strings.Contains(content, "my-broker") {
+		t.Fatalf("expected secrets to be redacted, got %q", content)
+	}
+	if !strings.Contains(content, "api_key=[REDACTED]") || !strings.Contains(content, "account_id=acct-") {
+		t.Fatalf("expected redaction markers, got %q", content)
+	}
+}
@@ -0,0 +1,97 @@
+package logging
+
+import (
+	"crypto/sha256"
+	"encoding/hex"
+	"log/slog"
+	"os"
+	"regexp"
+	"strings"
+	"sync"
+)
+
+const redacted = "[REDACTED]"
+
+var (
+	homeDirOnce sync.Once
+	homeDir     string
+
+	bearerPattern      = regexp.MustCompile(`(?i)(bearer\s+)[A-Za-z0-9._~+/=-]{8,}`)
+	apiKeyPattern      = regexp.MustCompile(`\b(sk-|AIza)[A-Za-z0-9_-]{8,}`)
+	secretPairPattern  = regexp.MustCompile(`(?i)("?(?:api[_-]?key|access[_-]?token|token|secret|password)"?\s*[:=]\s*"?)[^"\s,&}]+`)
+	accountPathPattern = regexp.MustCompile(`(/api/accounts/)[^/?\s"]+`)
+)
+
+// secretKeys are attribute keys whose values are never written, whatever they contain.
+var secretKeys = map[string]bool{
+	"api_key":       true,
+	"apikey":        true,
+	"token":         true,
+	"secret":        true,
+	"password":      true,
+	"authorization": true,
+}
+
+// accountKeys identify the user's brokerage accounts; they are replaced by a short
+// stable hash so log lines about the same account can still be correlated.
+var accountKeys = map[string]bool{
+	"account":      true,
+	"account_id":   true,
+	"account_name": true,
+}
+
+// Redact masks the user's home directory, API keys, bearer tokens, secret-looking
+// key/value pairs and account IDs in request paths, so logs are safe to share.
+func Redact(s string) string {
+	if s == "" {
+		return s
+	}
+	if home := userHome(); home != "" {
+		s = strings.ReplaceAll(s, home, "~")
+	}
+	s = bearerPattern.ReplaceAllString(s, "${1}"+redacted)
+	s = apiKeyPattern.ReplaceAllString(s, "${1}"+redacted)
+	s = secretPairPattern.ReplaceAllString(s, "${1}"+redacted)
+	s = accountPathPattern.ReplaceAllString(s, "${1}"+redacted)
+	return s
+}
+
+// redactAttr is the slog ReplaceAttr hook applied by every handler NewLogger builds.
+func redactAttr(_ []string, a slog.Attr) slog.Attr {
+	key := strings.ToLower(a.Key)
+	switch {
+	case secretKeys[key]:
+		return slog.String(a.Key, redacted)
+	case accountKeys[key]:
+		return slog.String(a.Key, hashIdentifier(a.Value.String()))
+	}
+
+	switch a.Value.Kind() {
+	case slog.KindString:
+		return slog.String(a.Key, Redact(a.Value.String()))
+	case slog.KindAny:
+		if err, ok := a.Value.Any().(error); ok && err != nil {
+			return slog.String(a.Key, Redact(err.Error()))
+		}
+	}
+	return a
+}
+
+func hashIdentifier(value string) string {
+	if value == "" {
+		return value
+	}
+	sum := sha256.Sum256([]byte(value))
+	return "acct-" + hex.EncodeToString(sum[:])[:8]
+}
+
+func userHome() string {
+	homeDirOnce.Do(func() {
+		home, err := os.UserHomeDir()
+		// A root-level home would turn every "/" into "~".
+		if err == nil && len(home) > 1 {
+			homeDir = home
+		}
+	})
+	return homeDir
+}
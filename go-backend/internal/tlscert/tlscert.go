@@ -0,0 +1,195 @@
+// Package tlscert manages the self-signed certificate the backend serves local HTTPS with.
+package tlscert
+
+import (
+	"crypto/ecdsa"
+	"crypto/elliptic"
+	"crypto/rand"
+	"crypto/tls"
+	"crypto/x509"
+	"crypto/x509/pkix"
+	"encoding/pem"
+	"errors"
+	"fmt"
+	"math/big"
+	"net"
+	"os"
+	"path/filepath"
+	"sort"
+	"strings"
+	"time"
+)
+
+const (
+	// CertFile and KeyFile are the names used inside the directory passed to Ensure.
+	CertFile = "cert.pem"
+	KeyFile  = "key.pem"
+
+	validity = 10 * 365 * 24 * time.Hour
+)
+
+// Ensure returns the certificate and key paths in dir, generating a new pair when either is
+// missing, unreadable or expired. The certificate covers 127.0.0.1, ::1, localhost, this
+// machine's LAN IPv4 addresses and its <hostname>.local name, so LAN clients can reach it
+// by address or Bonjour name; it is regenerated when those change (e.g. a new DHCP lease).
+// Clients pin it rather than trusting it through a CA.
+func Ensure(dir string) (certPath, keyPath string, err error) {
+	ips, names := subjectAltNames()
+	return ensure(dir, ips, names)
+}
+
+func ensure(dir string, ips []net.IP, names []string) (certPath, keyPath string, err error) {
+	certPath = filepath.Join(dir, CertFile)
+	keyPath = filepath.Join(dir, KeyFile)
+	if valid(certPath, keyPath, time.Now(), ips, names) {
+		return certPath, keyPath, nil
+	}
+	if err := os.MkdirAll(dir, 0o700); err != nil {
+		return "", "", err
+	}
+	if err := generate(certPath, keyPath, time.Now(), ips, names); err != nil {
+		return "", "", err
+	}
+	return certPath, keyPath, nil
+}
+
+// subjectAltNames lists what the certificate should cover on this machine right now.
+// IPv6 LAN addresses are left out: privacy addresses rotate and would regenerate it daily.
+func subjectAltNames() ([]net.IP, []string) {
+	ips := []net.IP{net.IPv4(127, 0, 0, 1), net.IPv6loopback}
+	ips = append(ips, lanAddresses()...)
+	names := []string{"localhost"}
+	if host, err := os.Hostname(); err == nil {
+		label := strings.ToLower(strings.SplitN(host, ".", 2)[0])
+		if label != "" && label != "localhost" {
+			names = append(names, label+".local")
+		}
+	}
+	return ips, names
+}
+
+func lanAddresses() []net.IP {
+	ifaces, err := net.Interfaces()
+	if err != nil {
+		return nil
+	}
+	var ips []net.IP
+	for _, iface := range ifaces {
+		if iface.Flags&net.FlagUp == 0 || iface.Flags&net.FlagLoopback != 0 {
+			continue
+		}
+		addrs, err := iface.Addrs()
+		if err != nil {
+			continue
+		}
+		for _, addr := range addrs {
+			ipNet, ok := addr.(*net.IPNet)
+			if !ok {
+				continue
+			}
+			ip := ipNet.IP.To4()
+			if ip == nil || ip.IsLoopback() || ip.IsLinkLocalUnicast() {
+				continue
+			}
+			ips = append(ips, ip)
+		}
+	}
+	return ips
+}
+
+func valid(certPath, keyPath string, now time.Time, ips []net.IP, names []string) bool {
+	pair, err := tls.LoadX509KeyPair(certPath, keyPath)
+	if err != nil || len(pair.Certificate) == 0 {
+		return false
+	}
+	cert, err := x509.ParseCertificate(pair.Certificate[0])
+	if err != nil {
+		return false
+	}
+	if !now.After(cert.NotBefore) || !now.Before(cert.NotAfter) {
+		return false
+	}
+	return sameSet(ipStrings(cert.IPAddresses), ipStrings(ips)) && sameSet(cert.DNSNames, names)
+}
+
+func ipStrings(ips []net.IP) []string {
+	out := make([]string, 0, len(ips))
+	for _, ip := range ips {
+		out = append(out, ip.String())
+	}
+	return out
+}
+
+func sameSet(a, b []string) bool {
+	a = uniqueSorted(a)
+	b = uniqueSorted(b)
+	if len(a) != len(b) {
+		return false
+	}
+	for i := range a {
+		if a[i] != b[i] {
+			return false
+		}
+	}
+	return true
+}
+
+func uniqueSorted(values []string) []string {
+	out := append([]string(nil), values...)
+	sort.Strings(out)
+	n := 0
+	for i, v := range out {
+		if i == 0 || v != out[n-1] {
+			out[n] = v
+			n++
+		}
+	}
+	return out[:n]
+}
+
+func generate(certPath, keyPath string, now time.Time, ips []net.IP, names []string) error {
+	key, err := ecdsa.GenerateKey(elliptic.P256(), rand.Reader)
+	if err != nil {
+		return fmt.Errorf("generate key: %w", err)
+	}
+	serial, err := rand.Int(rand.Reader, new(big.Int).Lsh(big.NewInt(1), 128))
+	if err != nil {
+		return fmt.Errorf("generate serial: %w", err)
+	}
+	template := &x509.Certificate{
+		SerialNumber:          serial,
+		Subject:               pkix.Name{CommonName: "Invest Log local backend"},
+		NotBefore:             now.Add(-time.Hour),
+		NotAfter:              now.Add(validity),
+		KeyUsage:              x509.KeyUsageDigitalSignature,
+		ExtKeyUsage:           []x509.ExtKeyUsage{x509.ExtKeyUsageServerAuth},
+		BasicConstraintsValid: true,
+		IPAddresses:           ips,
+		DNSNames:              names,
+	}
+	der, err := x509.CreateCertificate(rand.Reader, template, template, &key.PublicKey, key)
+	if err != nil {
+		return fmt.Errorf("create certificate: %w", err)
+	}
+	keyDER, err := x509.MarshalECPrivateKey(key)
+	if err != nil {
+		return fmt.Errorf("marshal key: %w", err)
+	}
+	// Key first: a cert without its key would be replaced on the next start anyway.
+	if err := writePEM(keyPath, "EC PRIVATE KEY", keyDER, 0o600); err != nil {
+		return err
+	}
+	return writePEM(certPath, "CERTIFICATE", der, 0o644)
+}
+
+func writePEM(path, blockType string, der []byte, mode os.FileMode) error {
+	tmp := path + ".tmp"
+	data := pem.EncodeToMemory(&pem.Block{Type: blockType, Bytes: der})
+	if data == nil {
+		return errors.New("encode " + blockType)
+	}
+	if err := os.WriteFile(tmp, data, mode); err != nil {
+		return err
+	}
+	return os.Rename(tmp, path)
+}
@@ -0,0 +1,161 @@
+package tlscert
+
+import (
+	"bytes"
+	"crypto/tls"
+	"crypto/x509"
+	"net"
+	"os"
+	"path/filepath"
+	"testing"
+	"time"
+)
+
+func TestEnsureGeneratesLoopbackCertificate(t *testing.T) {
+	dir := filepath.Join(t.TempDir(), "tls")
+	certPath, keyPath, err := Ensure(dir)
+	if err != nil {
+		t.Fatalf("Ensure: %v", err)
+	}
+
+	pair, err := tls.LoadX509KeyPair(certPath, keyPath)
+	if err != nil {
+		t.Fatalf("LoadX509KeyPair: %v", err)
+	}
+	cert, err := x509.ParseCertificate(pair.Certificate[0])
+	if err != nil {
+		t.Fatalf("ParseCertificate: %v", err)
+	}
+	if err := cert.VerifyHostname("127.0.0.1"); err != nil {
+		t.Fatalf("expected 127.0.0.1 to be covered: %v", err)
+	}
+	if err := cert.VerifyHostname("localhost"); err != nil {
+		t.Fatalf("expected localhost to be covered: %v", err)
+	}
+	if err := cert.VerifyHostname("example.com"); err == nil {
+		t.Fatal("expected other hosts to be rejected")
+	}
+	if !cert.NotAfter.After(time.Now().Add(365 * 24 * time.Hour)) {
+		t.Fatalf("expected long validity, got %v", cert.NotAfter)
+	}
+
+	info, err := os.Stat(keyPath)
+	if err != nil {
+		t.Fatalf("stat key: %v", err)
+	}
+	if info.Mode().Perm() != 0o600 {
+		t.Fatalf("expected key mode 0600, got %v", info.Mode().Perm())
+	}
+}
+
+func TestEnsureReusesAndRepairs(t *testing.T) {
+	dir := t.TempDir()
+	certPath, _, err := Ensure(dir)
+	if err != nil {
+		t.Fatalf("Ensure: %v", err)
+	}
+	first, err := os.ReadFile(certPath)
+	if err != nil {
+		t.Fatalf("read cert: %v", err)
+	}
+
+	if _, _, err := Ensure(dir); err != nil {
+		t.Fatalf("Ensure again: %v", err)
+	}
+	second, _ := os.ReadFile(certPath)
+	if !bytes.Equal(first, second) {
+		t.Fatal("expected existing certificate to be reused")
+	}
+
+	if err := os.WriteFile(filepath.Join(dir, KeyFile), []byte("garbage"), 0o600); err != nil {
+		t.Fatalf("corrupt key: %v", err)
+	}
+	if _, _, err := Ensure(dir); err != nil {
+		t.Fatalf("Ensure after corruption: %v", err)
+	}
+	third, _ := os.ReadFile(certPath)
+	if bytes.Equal(first, third) {
+		t.Fatal("expected a new certificate after the key was damaged")
+	}
+}
+
+func TestEnsureCoversAndTracksLANAddresses(t *testing.T) {
+	dir := t.TempDir()
+	loopback := []net.IP{net.IPv4(127, 0, 0, 1), net.IPv6loopback}
+	names := []string{"localhost", "studio.local"}
+
+	tests := []struct {
+		name       string
+		lan        string
+		wantReused bool
+	}{
+		{name: "initial", lan: "192.168.1.10"},
+		{name: "unchanged", lan: "192.168.1.10", wantReused: true},
+		{name: "new lease", lan: "192.168.1.20"},
+	}
+
+	var previous []byte
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			ips := append(append([]net.IP(nil), loopback...), net.ParseIP(tt.lan))
+			certPath, keyPath, err := ensure(dir, ips, names)
+			if err != nil {
+				t.Fatalf("ensure: %v", err)
+			}
+			current, err := os.ReadFile(certPath)
+			if err != nil {
+				t.Fatalf("read cert: %v", err)
+			}
+			if reused := bytes.Equal(previous, current); reused != tt.wantReused {
+				t.Fatalf("reused = %v, want %v", reused, tt.wantReused)
+			}
+			previous = current
+
+			pair, err := tls.LoadX509KeyPair(certPath, keyPath)
+			if err != nil {
+				t.Fatalf("LoadX509KeyPair: %v", err)
+			}
+			cert, err := x509.ParseCertificate(pair.Certificate[0])
+			if err != nil {
+				t.Fatalf("ParseCertificate: %v", err)
+			}
+			for _, host := range []string{"127.0.0.1", tt.lan, "studio.local"} {
+				if err := cert.VerifyHostname(host); err != nil {
+					t.Fatalf("expected %s to be covered: %v", host, err)
+				}
+			}
+		})
+	}
+}
+
+func TestServesTLSWithGeneratedCertificate(t *testing.T) {
+	certPath, keyPath, err := Ensure(t.TempDir())
+	if err != nil {
+		t.Fatalf("Ensure: %v", err)
+	}
+	pair, err := tls.LoadX509KeyPair(certPath, keyPath)
+	if err != nil {
+		t.Fatalf("LoadX509KeyPair: %v", err)
+	}
+	listener, err := tls.Listen("tcp", "127.0.0.1:0", &tls.Config{Certificates: []tls.Certificate{pair}})
+	if err != nil {
+		t.Fatalf("Listen: %v", err)
+	}
+	defer listener.Close()
+	go func() {
+		conn, err := listener.Accept()
+		if err == nil {
+			_ = conn.(*tls.Conn).Handshake()
+			conn.Close()
+		}
+	}()
+
+	pool := x509.NewCertPool()
+	leaf, _ := x509.ParseCertificate(pair.Certificate[0])
+	pool.AddCert(leaf)
+	conn, err := tls.Dial("tcp", listener.Addr().String(), &tls.Config{RootCAs: pool, ServerName: "127.0.0.1"})
+	if err != nil {
+		t.Fatalf("Dial with pinned certificate: %v", err)
+	}
+	conn.Close()
+}
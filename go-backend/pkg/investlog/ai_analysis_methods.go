@@ -24,6 +24,8 @@ type AIAnalysisMethod struct {
 type RunAIAnalysisRequest struct {
 	MethodID  int64
 	Variables map[string]string
+	// APIKey overrides the stored key, e.g. when the macOS shell keeps it in the Keychain.
+	APIKey string
 }
 
 func extractAIAnalysisVariables(systemPrompt, userPrompt string) []string {
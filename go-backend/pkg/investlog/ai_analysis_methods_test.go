@@ -168,3 +168,48 @@ func TestRunAIAnalysisStreamPersistsHistory(t *testing.T) {
 		t.Fatalf("unexpected variables in history: %#v", history[0].Variables)
 	}
 }
+
+func TestRunAIAnalysisUsesRequestAPIKey(t *testing.T) {
+	core, cleanup := setupTestDB(t)
+	defer cleanup()
+
+	server := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		if got := r.Header.Get("Authorization"); got != "Bearer keychain-key" {
+			t.Fatalf("unexpected authorization header: %q", got)
+		}
+		w.Header().Set("Content-Type", "application/json")
+		_, _ = w.Write([]byte(`{"model":"mock-model","choices":[{"message":{"content":"ok"}}]}`))
+	}))
+	defer server.Close()
+
+	_, err := core.SetAISettings(AISettings{
+		BaseURL: server.URL,
+		Model:   "mock-model",
+	})
+	assertNoError(t, err, "set ai settings")
+
+	method, err := core.CreateAIAnalysisMethod(AIAnalysisMethod{
+		Name:         "速览",
+		SystemPrompt: "分析 ${SYMBOL}",
+		UserPrompt:   "请回答",
+	})
+	assertNoError(t, err, "create method")
+
+	_, err = core.RunAIAnalysis(RunAIAnalysisRequest{
+		MethodID:  method.ID,
+		Variables: map[string]string{"SYMBOL": "AAPL"},
+	})
+	if err == nil || !strings.Contains(err.Error(), "API key is required") {
+		t.Fatalf("expected missing key error without override, got %v", err)
+	}
+
+	result, err := core.RunAIAnalysis(RunAIAnalysisRequest{
+		MethodID:  method.ID,
+		Variables: map[string]string{"SYMBOL": "AAPL"},
+		APIKey:    " keychain-key ",
+	})
+	assertNoError(t, err, "run ai analysis with request key")
+	if result.ResultText != "ok" {
+		t.Fatalf("unexpected result text: %q", result.ResultText)
+	}
+}
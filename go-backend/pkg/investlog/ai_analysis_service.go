@@ -108,6 +108,9 @@ func (c *Core) prepareAIAnalysis(req RunAIAnalysisRequest) (*AIAnalysisMethod, A
 	if err != nil {
 		return nil, AISettings{}, "", "", nil, err
 	}
+	if apiKey := strings.TrimSpace(req.APIKey); apiKey != "" {
+		settings.APIKey = apiKey
+	}
 	if strings.TrimSpace(settings.APIKey) == "" {
 		return nil, AISettings{}, "", "", nil, fmt.Errorf("AI API key is required")
 	}
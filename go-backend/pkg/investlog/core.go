@@ -119,6 +119,15 @@ func (c *Core) DBPath() string {
 	return c.dbPath
 }
 
+// QuickCheck runs SQLite's quick_check and returns its first result row ("ok" when healthy).
+func (c *Core) QuickCheck() (string, error) {
+	var result string
+	if err := c.db.QueryRow("PRAGMA quick_check").Scan(&result); err != nil {
+		return "", fmt.Errorf("quick_check: %w", err)
+	}
+	return result, nil
+}
+
 // Logger returns the Core logger, falling back to the default logger.
 func (c *Core) Logger() *slog.Logger {
 	if c == nil || c.logger == nil {
@@ -41,3 +41,24 @@ func TestDefaultHelpers(t *testing.T) {
 		t.Fatalf("defaultInt value: %d", got)
 	}
 }
+
+func TestQuickCheck(t *testing.T) {
+	core, err := Open(filepath.Join(t.TempDir(), "check.db"))
+	if err != nil {
+		t.Fatalf("Open: %v", err)
+	}
+	result, err := core.QuickCheck()
+	if err != nil {
+		t.Fatalf("QuickCheck: %v", err)
+	}
+	if result != "ok" {
+		t.Fatalf("expected ok, got %q", result)
+	}
+
+	if err := core.Close(); err != nil {
+		t.Fatalf("Close: %v", err)
+	}
+	if _, err := core.QuickCheck(); err == nil {
+		t.Fatalf("expected error on closed db")
+	}
+}
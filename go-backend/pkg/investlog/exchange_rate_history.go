@@ -0,0 +1,317 @@
+package investlog
+
+import (
+	"context"
+	"database/sql"
+	"fmt"
+	"net/http"
+	"strings"
+	"time"
+)
+
+const (
+	exchangeRateDateLayout = "2006-01-02"
+
+	// Long Frankfurter time series are thinned out, so history is fetched in short windows.
+	exchangeRateBackfillWindowDays = 90
+)
+
+var exchangeRateHistoryFetcher = fetchExchangeRateHistoryFromFrankfurter
+
+// GetExchangeRateOn returns the rate between two supported currencies on date (YYYY-MM-DD,
+// today when empty) from the cached history, so past valuations work offline. Each leg uses
+// the nearest cached day, preferring the latest one on or before date, and falls back to
+// the maintained rate when nothing has been cached yet.
+func (c *Core) GetExchangeRateOn(date, fromCurrency, toCurrency string) (HistoricalExchangeRate, error) {
+	date = strings.TrimSpace(date)
+	if date == "" {
+		date = TodayISOInShanghai()
+	}
+	if _, err := time.Parse(exchangeRateDateLayout, date); err != nil {
+		return HistoricalExchangeRate{}, fmt.Errorf("invalid date: %s", date)
+	}
+	fromCurrency = normalizeCurrency(fromCurrency)
+	toCurrency = normalizeCurrency(toCurrency)
+	for _, currency := range []string{fromCurrency, toCurrency} {
+		if !isValidCurrency(currency) {
+			return HistoricalExchangeRate{}, fmt.Errorf("invalid currency: %s", currency)
+		}
+	}
+
+	result := HistoricalExchangeRate{
+		FromCurrency: fromCurrency,
+		ToCurrency:   toCurrency,
+		Date:         date,
+		Rate:         NewAmount(1),
+		RateDate:     date,
+	}
+	if fromCurrency == toCurrency {
+		return result, nil
+	}
+
+	fromRate, fromRateDate, err := c.rateToCNYOn(fromCurrency, date)
+	if err != nil {
+		return HistoricalExchangeRate{}, fmt.Errorf("get %s rate: %w", fromCurrency, err)
+	}
+	toRate, toRateDate, err := c.rateToCNYOn(toCurrency, date)
+	if err != nil {
+		return HistoricalExchangeRate{}, fmt.Errorf("get %s rate: %w", toCurrency, err)
+	}
+	result.Rate = NewAmount(fromRate / toRate)
+	result.RateDate = olderRateDate(fromRateDate, toRateDate)
+	return result, nil
+}
+
+// BackfillExchangeRateHistory fetches daily rates for each foreign currency used in
+// transactions, from its first transaction date up to today. A watermark records how far
+// fetched windows reach without a gap, so a run resumes after the last window that
+// succeeded rather than after the newest cached day. Days already cached are left untouched.
+func (c *Core) BackfillExchangeRateHistory() (int, []string, error) {
+	rows, err := c.db.Query(`
+		SELECT currency, MIN(transaction_date)
+		FROM transactions
+		WHERE currency IN ('USD', 'HKD')
+		GROUP BY currency
+		ORDER BY currency
+	`)
+	if err != nil {
+		return 0, nil, err
+	}
+	type backfillTarget struct {
+		currency string
+		start    time.Time
+	}
+	targets := []backfillTarget{}
+	for rows.Next() {
+		var currency string
+		var firstDate sql.NullString
+		if err := rows.Scan(&currency, &firstDate); err != nil {
+			rows.Close()
+			return 0, nil, err
+		}
+		start, err := parseExchangeRateDate(firstDate.String)
+		if err != nil {
+			continue
+		}
+		targets = append(targets, backfillTarget{currency: currency, start: start})
+	}
+	if err := rows.Err(); err != nil {
+		rows.Close()
+		return 0, nil, err
+	}
+	rows.Close()
+
+	today, err := parseExchangeRateDate(TodayISOInShanghai())
+	if err != nil {
+		return 0, nil, err
+	}
+
+	inserted := 0
+	errors := []string{}
+	for _, target := range targets {
+		start, err := c.exchangeRateBackfillStart(target.currency, target.start)
+		if err != nil {
+			return inserted, errors, err
+		}
+		for windowStart := start; !windowStart.After(today); windowStart = windowStart.AddDate(0, 0, exchangeRateBackfillWindowDays) {
+			windowEnd := windowStart.AddDate(0, 0, exchangeRateBackfillWindowDays-1)
+			if windowEnd.After(today) {
+				windowEnd = today
+			}
+			rates, err := exchangeRateHistoryFetcher(target.currency, "CNY", windowStart, windowEnd)
+			if err != nil {
+				errors = append(errors, fmt.Sprintf(
+					"%s/CNY %s..%s: %v",
+					target.currency,
+					windowStart.Format(exchangeRateDateLayout),
+					windowEnd.Format(exchangeRateDateLayout),
+					err,
+				))
+				break
+			}
+			count, err := c.insertExchangeRateHistory(target.currency, "CNY", rates)
+			if err != nil {
+				return inserted, errors, err
+			}
+			inserted += count
+			if err := c.markExchangeRateBackfilled(target.currency, "CNY", start, windowEnd); err != nil {
+				return inserted, errors, err
+			}
+		}
+	}
+	return inserted, errors, nil
+}
+
+func (c *Core) recordExchangeRateHistory(fromCurrency, toCurrency, date string, rate float64, source string) error {
+	tx, err := c.db.Begin()
+	if err != nil {
+		return err
+	}
+	defer func() {
+		_ = tx.Rollback()
+	}()
+	if err := recordExchangeRateHistoryTx(tx, fromCurrency, toCurrency, date, rate, source); err != nil {
+		return err
+	}
+	return tx.Commit()
+}
+
+func recordExchangeRateHistoryTx(tx *sql.Tx, fromCurrency, toCurrency, date string, rate float64, source string) error {
+	_, err := tx.Exec(`
+		INSERT INTO exchange_rate_history (from_currency, to_currency, rate_date, rate, source)
+		VALUES (?, ?, ?, ?, ?)
+		ON CONFLICT(from_currency, to_currency, rate_date) DO UPDATE SET
+			rate = excluded.rate,
+			source = excluded.source
+	`, fromCurrency, toCurrency, date, rate, source)
+	return err
+}
+
+func (c *Core) insertExchangeRateHistory(fromCurrency, toCurrency string, rates map[string]float64) (int, error) {
+	tx, err := c.db.Begin()
+	if err != nil {
+		return 0, err
+	}
+	defer func() {
+		_ = tx.Rollback()
+	}()
+
+	inserted := 0
+	for date, rate := range rates {
+		if rate <= 0 {
+			continue
+		}
+		if _, err := time.Parse(exchangeRateDateLayout, date); err != nil {
+			continue
+		}
+		res, err := tx.Exec(`
+			INSERT OR IGNORE INTO exchange_rate_history (from_currency, to_currency, rate_date, rate, source)
+			VALUES (?, ?, ?, ?, ?)
+		`, fromCurrency, toCurrency, date, rate, exchangeRateSourceAutoFetch)
+		if err != nil {
+			return 0, err
+		}
+		if affected, err := res.RowsAffected(); err == nil {
+			inserted += int(affected)
+		}
+	}
+	if err := tx.Commit(); err != nil {
+		return 0, err
+	}
+	return inserted, nil
+}
+
+// rateToCNYOn returns the cached currency→CNY rate nearest to date and the day it was
+// observed; the day is empty when the maintained rate was used instead.
+func (c *Core) rateToCNYOn(currency, date string) (float64, string, error) {
+	if currency == "CNY" {
+		return 1, date, nil
+	}
+	if err := validateExchangeRatePair(currency, "CNY"); err != nil {
+		return 0, "", err
+	}
+
+	var rate float64
+	var rateDate string
+	err := c.db.QueryRow(`
+		SELECT rate, rate_date
+		FROM exchange_rate_history
+		WHERE from_currency = ? AND to_currency = 'CNY'
+		ORDER BY CASE WHEN rate_date <= ? THEN 0 ELSE 1 END, ABS(julianday(rate_date) - julianday(?))
+		LIMIT 1
+	`, currency, date, date).Scan(&rate, &rateDate)
+	if err == sql.ErrNoRows {
+		rate, err = c.GetRateToCNY(currency)
+		return rate, "", err
+	}
+	if err != nil {
+		return 0, "", err
+	}
+	return rate, rateDate, nil
+}
+
+// exchangeRateBackfillStart resumes after the watermark, or starts over from the first
+// transaction when there is none or it begins later than that transaction.
+func (c *Core) exchangeRateBackfillStart(currency string, firstTransaction time.Time) (time.Time, error) {
+	var coveredFrom, coveredUntil string
+	err := c.db.QueryRow(`
+		SELECT covered_from, covered_until
+		FROM exchange_rate_backfill
+		WHERE from_currency = ? AND to_currency = 'CNY'
+	`, currency).Scan(&coveredFrom, &coveredUntil)
+	if err == sql.ErrNoRows {
+		return firstTransaction, nil
+	}
+	if err != nil {
+		return time.Time{}, err
+	}
+	fromDate, err := parseExchangeRateDate(coveredFrom)
+	if err != nil || fromDate.After(firstTransaction) {
+		return firstTransaction, nil
+	}
+	untilDate, err := parseExchangeRateDate(coveredUntil)
+	if err != nil {
+		return firstTransaction, nil
+	}
+	return untilDate.AddDate(0, 0, 1), nil
+}
+
+// markExchangeRateBackfilled extends the watermark after a window succeeded. runStart is
+// where this run began: the old start is kept when resuming, replaced when starting over.
+func (c *Core) markExchangeRateBackfilled(fromCurrency, toCurrency string, runStart, windowEnd time.Time) error {
+	_, err := c.db.Exec(`
+		INSERT INTO exchange_rate_backfill (from_currency, to_currency, covered_from, covered_until)
+		VALUES (?, ?, ?, ?)
+		ON CONFLICT(from_currency, to_currency) DO UPDATE SET
+			covered_from = MIN(covered_from, excluded.covered_from),
+			covered_until = excluded.covered_until
+	`, fromCurrency, toCurrency, runStart.Format(exchangeRateDateLayout), windowEnd.Format(exchangeRateDateLayout))
+	return err
+}
+
+func parseExchangeRateDate(value string) (time.Time, error) {
+	value = strings.TrimSpace(value)
+	if len(value) > len(exchangeRateDateLayout) {
+		value = value[:len(exchangeRateDateLayout)]
+	}
+	return time.Parse(exchangeRateDateLayout, value)
+}
+
+func olderRateDate(a, b string) string {
+	if a == "" || b == "" {
+		return ""
+	}
+	if a < b {
+		return a
+	}
+	return b
+}
+
+type frankfurterTimeSeriesResponse struct {
+	Rates map[string]map[string]float64 `json:"rates"`
+}
+
+func fetchExchangeRateHistoryFromFrankfurter(fromCurrency, toCurrency string, start, end time.Time) (map[string]float64, error) {
+	client := &http.Client{Timeout: exchangeRateRequestTimeout}
+	ctx, cancel := context.WithTimeout(context.Background(), exchangeRateRequestTimeout)
+	defer cancel()
+
+	url := fmt.Sprintf(
+		"https://api.frankfurter.app/%s..%s?from=%s&to=%s",
+		start.Format(exchangeRateDateLayout),
+		end.Format(exchangeRateDateLayout),
+		fromCurrency,
+		toCurrency,
+	)
+	var payload frankfurterTimeSeriesResponse
+	if err := fetchJSONWithClient(ctx, client, url, &payload); err != nil {
+		return nil, err
+	}
+	rates := make(map[string]float64, len(payload.Rates))
+	for date, byCurrency := range payload.Rates {
+		if rate := byCurrency[toCurrency]; rate > 0 {
+			rates[date] = rate
+		}
+	}
+	return rates, nil
+}
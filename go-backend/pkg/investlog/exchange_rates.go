@@ -69,7 +69,16 @@ func (c *Core) SetExchangeRate(fromCurrency, toCurrency string, rate float64, so
 	}
 	normalizedSource := normalizeExchangeRateSource(source)
 
-	_, err := c.db.Exec(`
+	// The current rate and today's history row change together, so history never disagrees
+	// with the rate in use.
+	tx, err := c.db.Begin()
+	if err != nil {
+		return false, err
+	}
+	defer func() {
+		_ = tx.Rollback()
+	}()
+	_, err = tx.Exec(`
 		INSERT INTO exchange_rates (from_currency, to_currency, rate, source, updated_at)
 		VALUES (?, ?, ?, ?, CURRENT_TIMESTAMP)
 		ON CONFLICT(from_currency, to_currency) DO UPDATE SET
@@ -80,6 +89,12 @@ func (c *Core) SetExchangeRate(fromCurrency, toCurrency string, rate float64, so
 	if err != nil {
 		return false, err
 	}
+	if err := recordExchangeRateHistoryTx(tx, fromCurrency, toCurrency, TodayISOInShanghai(), rate, normalizedSource); err != nil {
+		return false, err
+	}
+	if err := tx.Commit(); err != nil {
+		return false, err
+	}
 	c.invalidateHoldingsCache()
 	return true, nil
 }
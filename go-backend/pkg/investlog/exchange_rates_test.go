@@ -4,6 +4,7 @@ import (
 	"fmt"
 	"strings"
 	"testing"
+	"time"
 )
 
 func TestGetExchangeRates_Defaults(t *testing.T) {
@@ -141,3 +142,228 @@ func TestRefreshExchangeRates(t *testing.T) {
 		t.Fatalf("unexpected HKD/CNY rate, got %.6f", hkdRate)
 	}
 }
+
+func TestGetExchangeRateOn(t *testing.T) {
+	core, cleanup := setupTestDB(t)
+	defer cleanup()
+
+	for _, item := range []struct {
+		date string
+		rate float64
+	}{
+		{date: "2024-01-02", rate: 7.10},
+		{date: "2024-01-05", rate: 7.15},
+	} {
+		if err := core.recordExchangeRateHistory("USD", "CNY", item.date, item.rate, exchangeRateSourceAutoFetch); err != nil {
+			t.Fatalf("recordExchangeRateHistory returned error: %v", err)
+		}
+	}
+
+	tests := []struct {
+		name         string
+		date         string
+		from         string
+		to           string
+		wantRate     float64
+		wantRateDate string
+	}{
+		{name: "latest on or before", date: "2024-01-03", from: "USD", to: "CNY", wantRate: 7.10, wantRateDate: "2024-01-02"},
+		{name: "exact day", date: "2024-01-05", from: "usd", to: "cny", wantRate: 7.15, wantRateDate: "2024-01-05"},
+		{name: "before history", date: "2023-12-01", from: "USD", to: "CNY", wantRate: 7.10, wantRateDate: "2024-01-02"},
+		{name: "inverse", date: "2024-01-03", from: "CNY", to: "USD", wantRate: 1 / 7.10, wantRateDate: "2024-01-02"},
+		{name: "maintained rate without history", date: "2024-01-03", from: "HKD", to: "CNY", wantRate: defaultHKDToCNYRate, wantRateDate: ""},
+		{name: "same currency", date: "2024-01-03", from: "USD", to: "USD", wantRate: 1, wantRateDate: "2024-01-03"},
+	}
+
+	for _, tc := range tests {
+		t.Run(tc.name, func(t *testing.T) {
+			got, err := core.GetExchangeRateOn(tc.date, tc.from, tc.to)
+			if err != nil {
+				t.Fatalf("GetExchangeRateOn returned error: %v", err)
+			}
+			assertFloatEquals(t, got.Rate, tc.wantRate, "rate")
+			if got.RateDate != tc.wantRateDate {
+				t.Fatalf("expected rate_date %q, got %q", tc.wantRateDate, got.RateDate)
+			}
+		})
+	}
+
+	for _, tc := range []struct {
+		name    string
+		date    string
+		from    string
+		wantErr string
+	}{
+		{name: "invalid date", date: "2024-13-01", from: "USD", wantErr: "invalid date"},
+		{name: "invalid currency", date: "2024-01-03", from: "EUR", wantErr: "invalid currency"},
+	} {
+		t.Run(tc.name, func(t *testing.T) {
+			_, err := core.GetExchangeRateOn(tc.date, tc.from, "CNY")
+			if err == nil || !strings.Contains(err.Error(), tc.wantErr) {
+				t.Fatalf("expected error containing %q, got %v", tc.wantErr, err)
+			}
+		})
+	}
+}
+
+func TestSetExchangeRate_RecordsHistory(t *testing.T) {
+	core, cleanup := setupTestDB(t)
+	defer cleanup()
+
+	if _, err := core.SetExchangeRate("USD", "CNY", 7.31, "manual"); err != nil {
+		t.Fatalf("SetExchangeRate returned error: %v", err)
+	}
+
+	got, err := core.GetExchangeRateOn("", "USD", "CNY")
+	if err != nil {
+		t.Fatalf("GetExchangeRateOn returned error: %v", err)
+	}
+	assertFloatEquals(t, got.Rate, 7.31, "rate")
+	if got.RateDate != TodayISOInShanghai() {
+		t.Fatalf("expected rate_date %s, got %q", TodayISOInShanghai(), got.RateDate)
+	}
+}
+
+func TestBackfillExchangeRateHistory(t *testing.T) {
+	core, cleanup := setupTestDB(t)
+	defer cleanup()
+
+	originalFetcher := exchangeRateHistoryFetcher
+	defer func() {
+		exchangeRateHistoryFetcher = originalFetcher
+	}()
+
+	calls := 0
+	exchangeRateHistoryFetcher = func(fromCurrency, toCurrency string, start, end time.Time) (map[string]float64, error) {
+		calls++
+		if fromCurrency != "USD" || toCurrency != "CNY" {
+			return nil, fmt.Errorf("unexpected pair: %s/%s", fromCurrency, toCurrency)
+		}
+		rates := map[string]float64{}
+		for day := start; !day.After(end); day = day.AddDate(0, 0, 1) {
+			rates[day.Format(exchangeRateDateLayout)] = 7.2
+		}
+		return rates, nil
+	}
+
+	today, err := time.Parse(exchangeRateDateLayout, TodayISOInShanghai())
+	if err != nil {
+		t.Fatalf("parse today: %v", err)
+	}
+	first := today.AddDate(0, 0, -10)
+
+	testAccount(t, core, "acc1", "Account 1")
+	if _, err := core.AddTransaction(AddTransactionRequest{
+		TransactionDate: first.Format(exchangeRateDateLayout),
+		Symbol:          "AAPL",
+		TransactionType: "BUY",
+		Quantity:        NewAmount(1),
+		Price:           NewAmount(100),
+		Currency:        "USD",
+		AccountID:       "acc1",
+		AssetType:       "stock",
+	}); err != nil {
+		t.Fatalf("AddTransaction returned error: %v", err)
+	}
+
+	manualDay := first.AddDate(0, 0, 2).Format(exchangeRateDateLayout)
+	if err := core.recordExchangeRateHistory("USD", "CNY", manualDay, 9.99, exchangeRateSourceManual); err != nil {
+		t.Fatalf("recordExchangeRateHistory returned error: %v", err)
+	}
+
+	inserted, errors, err := core.BackfillExchangeRateHistory()
+	if err != nil {
+		t.Fatalf("BackfillExchangeRateHistory returned error: %v", err)
+	}
+	if len(errors) != 0 {
+		t.Fatalf("expected no errors, got %v", errors)
+	}
+	if inserted != 10 {
+		t.Fatalf("expected 10 inserted days, got %d", inserted)
+	}
+
+	manual, err := core.GetExchangeRateOn(manualDay, "USD", "CNY")
+	if err != nil {
+		t.Fatalf("GetExchangeRateOn returned error: %v", err)
+	}
+	assertFloatEquals(t, manual.Rate, 9.99, "cached rate kept")
+
+	calls = 0
+	inserted, _, err = core.BackfillExchangeRateHistory()
+	if err != nil {
+		t.Fatalf("second BackfillExchangeRateHistory returned error: %v", err)
+	}
+	if inserted != 0 || calls != 0 {
+		t.Fatalf("expected nothing to fetch once up to date, got inserted=%d calls=%d", inserted, calls)
+	}
+}
+
+func TestBackfillExchangeRateHistory_FillsGapAfterFailedWindow(t *testing.T) {
+	core, cleanup := setupTestDB(t)
+	defer cleanup()
+
+	originalFetcher := exchangeRateHistoryFetcher
+	defer func() {
+		exchangeRateHistoryFetcher = originalFetcher
+	}()
+
+	today, err := time.Parse(exchangeRateDateLayout, TodayISOInShanghai())
+	if err != nil {
+		t.Fatalf("parse today: %v", err)
+	}
+	first := today.AddDate(0, 0, -(exchangeRateBackfillWindowDays + 30))
+	secondWindow := first.AddDate(0, 0, exchangeRateBackfillWindowDays)
+
+	failSecondWindow := true
+	exchangeRateHistoryFetcher = func(fromCurrency, toCurrency string, start, end time.Time) (map[string]float64, error) {
+		if failSecondWindow && start.Equal(secondWindow) {
+			return nil, fmt.Errorf("provider unavailable")
+		}
+		rates := map[string]float64{}
+		for day := start; !day.After(end); day = day.AddDate(0, 0, 1) {
+			rates[day.Format(exchangeRateDateLayout)] = 7.2
+		}
+		return rates, nil
+	}
+
+	testAccount(t, core, "acc1", "Account 1")
+	if _, err := core.AddTransaction(AddTransactionRequest{
+		TransactionDate: first.Format(exchangeRateDateLayout),
+		Symbol:          "AAPL",
+		TransactionType: "BUY",
+		Quantity:        NewAmount(1),
+		Price:           NewAmount(100),
+		Currency:        "USD",
+		AccountID:       "acc1",
+		AssetType:       "stock",
+	}); err != nil {
+		t.Fatalf("AddTransaction returned error: %v", err)
+	}
+
+	_, errors, err := core.BackfillExchangeRateHistory()
+	if err != nil {
+		t.Fatalf("BackfillExchangeRateHistory returned error: %v", err)
+	}
+	if len(errors) != 1 {
+		t.Fatalf("expected one window error, got %v", errors)
+	}
+
+	// A live refresh caches today's rate before the next backfill runs.
+	if _, err := core.SetExchangeRate("USD", "CNY", 7.3, exchangeRateSourceAutoFetch); err != nil {
+		t.Fatalf("SetExchangeRate returned error: %v", err)
+	}
+
+	failSecondWindow = false
+	if _, errors, err = core.BackfillExchangeRateHistory(); err != nil || len(errors) != 0 {
+		t.Fatalf("second BackfillExchangeRateHistory: err=%v errors=%v", err, errors)
+	}
+
+	gapDay := secondWindow.AddDate(0, 0, 5).Format(exchangeRateDateLayout)
+	result, err := core.GetExchangeRateOn(gapDay, "USD", "CNY")
+	if err != nil {
+		t.Fatalf("GetExchangeRateOn returned error: %v", err)
+	}
+	if result.RateDate != gapDay {
+		t.Fatalf("expected gap day %s to be cached, got rate from %q", gapDay, result.RateDate)
+	}
+}
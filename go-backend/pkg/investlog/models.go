@@ -220,6 +220,17 @@ type ExchangeRateSetting struct {
 	UpdatedAt    string `json:"updated_at"`
 }
 
+// HistoricalExchangeRate is the rate used to convert between two currencies on a date.
+// RateDate is the day the cached rate was observed; it can differ from Date on weekends
+// and holidays, and is empty when no history exists and the maintained rate was used.
+type HistoricalExchangeRate struct {
+	FromCurrency string `json:"from_currency"`
+	ToCurrency   string `json:"to_currency"`
+	Date         string `json:"date"`
+	Rate         Amount `json:"rate"`
+	RateDate     string `json:"rate_date"`
+}
+
 // AISettings represents persisted AI analysis configuration.
 type AISettings struct {
 	BaseURL         string `json:"base_url"`
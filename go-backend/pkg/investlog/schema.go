@@ -163,6 +163,43 @@ func initDatabase(db *sql.DB) error {
 		}
 	}
 
+	if err := exec(tx, `
+		CREATE TABLE IF NOT EXISTS exchange_rate_history (
+			from_currency TEXT NOT NULL CHECK(from_currency IN ('USD', 'HKD')),
+			to_currency TEXT NOT NULL CHECK(to_currency = 'CNY'),
+			rate_date TEXT NOT NULL,
+			rate REAL NOT NULL CHECK(rate > 0),
+			source TEXT NOT NULL DEFAULT 'manual',
+			PRIMARY KEY(from_currency, to_currency, rate_date)
+		)
+	`); err != nil {
+		return err
+	}
+
+	// How far back-filled history reaches, contiguously, per pair. Kept apart from the
+	// history rows because live refreshes add today's rate to those on every run.
+	if err := exec(tx, `
+		CREATE TABLE IF NOT EXISTS exchange_rate_backfill (
+			from_currency TEXT NOT NULL,
+			to_currency TEXT NOT NULL,
+			covered_from TEXT NOT NULL,
+			covered_until TEXT NOT NULL,
+			PRIMARY KEY(from_currency, to_currency)
+		)
+	`); err != nil {
+		return err
+	}
+
+	// Seed history with rates that were fetched or entered before it existed.
+	if err := exec(tx, `
+		INSERT OR IGNORE INTO exchange_rate_history (from_currency, to_currency, rate_date, rate, source)
+		SELECT from_currency, to_currency, date(updated_at), rate, source
+		FROM exchange_rates
+		WHERE source != 'default'
+	`); err != nil {
+		return err
+	}
+
 	if err := exec(tx, `
 		CREATE TABLE IF NOT EXISTS ai_settings (
 			id INTEGER PRIMARY KEY CHECK(id = 1),
@@ -0,0 +1,173 @@
+import AppKit
+import LocalAuthentication
+
+/// Optional app lock: while locked every registered window is covered by an opaque overlay
+/// until macOS authenticates the user with Touch ID or the login password. Nothing leaves
+/// the machine; LocalAuthentication only answers yes or no.
+final class AppLock {
+  static var isEnabled: Bool {
+    get { return UserDefaults.standard.bool(forKey: "AppLockEnabled") }
+    set { UserDefaults.standard.set(newValue, forKey: "AppLockEnabled") }
+  }
+
+  /// Minutes without input in any app window before locking; 0 disables auto-lock.
+  static var idleMinutes: Int {
+    get { return UserDefaults.standard.object(forKey: "AutoLockMinutes") as? Int ?? 5 }
+    set { UserDefaults.standard.set(newValue, forKey: "AutoLockMinutes") }
+  }
+
+  /// Touch ID, Apple Watch or the account password are all acceptable.
+  static var isAvailable: Bool {
+    return LAContext().canEvaluatePolicy(.deviceOwnerAuthentication, error: nil)
+  }
+
+  private(set) var isLocked = false
+  private var overlays: [ObjectIdentifier: LockOverlayView] = [:]
+  private var authenticating = false
+  /// Set after the user cancels, so reactivating the app doesn't re-prompt in a loop.
+  private(set) var promptDismissed = false
+  private var lastActivity = Date()
+  private var idleTimer: Timer?
+  private var activityMonitor: Any?
+
+  var onUnlock: (() -> Void)?
+
+  func lock(_ windows: [NSWindow], reason: String) {
+    guard AppLock.isEnabled, !isLocked else { return }
+    isLocked = true
+    promptDismissed = false
+    windows.forEach(cover)
+    ShellLog.info("app locked", ["reason": reason])
+  }
+
+  /// Also used for windows opened while the app is locked.
+  func cover(_ window: NSWindow) {
+    guard isLocked, overlays[ObjectIdentifier(window)] == nil, let content = window.contentView else { return }
+    let overlay = LockOverlayView(frame: content.bounds)
+    overlay.autoresizingMask = [.width, .height]
+    overlay.onUnlock = { [weak self] in self?.unlock() }
+    content.addSubview(overlay)
+    // Keep keystrokes away from whatever field the page had focused.
+    window.makeFirstResponder(overlay)
+    overlays[ObjectIdentifier(window)] = overlay
+  }
+
+  func unlock() {
+    guard isLocked, !authenticating else { return }
+    authenticating = true
+    AppLock.authenticate(reason: "unlock Invest Log") { [weak self] success in
+      guard let self = self else { return }
+      self.authenticating = false
+      guard success else {
+        self.promptDismissed = true
+        return
+      }
+      self.isLocked = false
+      self.lastActivity = Date()
+      self.overlays.values.forEach { $0.removeFromSuperview() }
+      self.overlays = [:]
+      ShellLog.info("app unlocked")
+      self.onUnlock?()
+    }
+  }
+
+  /// Input only reaches local monitors while the app is active, so time spent in other
+  /// apps counts as idle too.
+  func startIdleMonitor(onIdle: @escaping () -> Void) {
+    activityMonitor = NSEvent.addLocalMonitorForEvents(
+      matching: [.keyDown, .leftMouseDown, .rightMouseDown, .scrollWheel, .mouseMoved]
+    ) { [weak self] event in
+      self?.noteActivity()
+      return event
+    }
+    idleTimer = Timer.scheduledTimer(withTimeInterval: 15, repeats: true) { [weak self] _ in
+      guard let self = self, !self.isLocked, AppLock.isEnabled, AppLock.idleMinutes > 0 else { return }
+      if Date().timeIntervalSince(self.lastActivity) >= TimeInterval(AppLock.idleMinutes * 60) {
+        onIdle()
+      }
+    }
+  }
+
+  func noteActivity() {
+    lastActivity = Date()
+  }
+
+  /// Calls back on the main thread.
+  static func authenticate(reason: String, completion: @escaping (Bool) -> Void) {
+    let context = LAContext()
+    context.evaluatePolicy(.deviceOwnerAuthentication, localizedReason: reason) { success, error in
+      if let error = error {
+        ShellLog.warn("authentication failed", ["err": error.localizedDescription])
+      }
+      DispatchQueue.main.async { completion(success) }
+    }
+  }
+}
+
+/// Opaque cover with an Unlock button; swallows clicks and keys aimed at the page beneath.
+final class LockOverlayView: NSView {
+  var onUnlock: (() -> Void)?
+
+  override init(frame frameRect: NSRect) {
+    super.init(frame: frameRect)
+    wantsLayer = true
+
+    let icon = NSImageView()
+    if #available(macOS 11.0, *) {
+      icon.image = NSImage(systemSymbolName: "lock.fill", accessibilityDescription: "Locked")
+      icon.symbolConfiguration = NSImage.SymbolConfiguration(pointSize: 40, weight: .regular)
+    }
+    icon.contentTintColor = .secondaryLabelColor
+    let label = NSTextField(labelWithString: "Invest Log is locked")
+    label.font = .systemFont(ofSize: 17, weight: .semibold)
+    let button = NSButton(title: "Unlock", target: self, action: #selector(unlock(_:)))
+    button.bezelStyle = .rounded
+    button.keyEquivalent = "\r"
+
+    let stack = NSStackView(views: [icon, label, button])
+    stack.orientation = .vertical
+    stack.spacing = 12
+    stack.translatesAutoresizingMaskIntoConstraints = false
+    addSubview(stack)
+    NSLayoutConstraint.activate([
+      stack.centerXAnchor.constraint(equalTo: centerXAnchor),
+      stack.centerYAnchor.constraint(equalTo: centerYAnchor),
+    ])
+  }
+
+  required init?(coder: NSCoder) {
+    fatalError("init(coder:) has not been implemented")
+  }
+
+  override var wantsUpdateLayer: Bool {
+    return true
+  }
+
+  override func updateLayer() {
+    layer?.backgroundColor = NSColor.windowBackgroundColor.cgColor
+  }
+
+  override var acceptsFirstResponder: Bool {
+    return true
+  }
+
+  override func keyDown(with event: NSEvent) {
+    if event.keyCode == 36 || event.keyCode == 76 {
+      onUnlock?()
+    }
+  }
+
+  // The overlay may sit inside the web view, so unhandled events would reach the page.
+  override func mouseDown(with event: NSEvent) {}
+  override func rightMouseDown(with event: NSEvent) {}
+  override func otherMouseDown(with event: NSEvent) {}
+  override func scrollWheel(with event: NSEvent) {}
+
+  override func menu(for event: NSEvent) -> NSMenu? {
+    return nil
+  }
+
+  @objc private func unlock(_ sender: Any?) {
+    onUnlock?()
+  }
+}
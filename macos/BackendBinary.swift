@@ -0,0 +1,39 @@
+import Foundation
+
+/// Picks the backend build matching this Mac: the bundle ships `invest-log-backend-arm64` and
+/// `invest-log-backend-x86_64`, so an Apple Silicon Mac never runs the Intel one under
+/// Rosetta. A plain `invest-log-backend` (single-architecture builds) is used when no
+/// per-architecture file is present at all.
+enum BackendBinary {
+  static let baseName = "invest-log-backend"
+
+  /// The hardware architecture, even when the shell itself was started under Rosetta.
+  static var hostArchitecture: String {
+    var value: Int32 = 0
+    var size = MemoryLayout<Int32>.size
+    if sysctlbyname("hw.optional.arm64", &value, &size, nil, 0) == 0, value == 1 {
+      return "arm64"
+    }
+    return "x86_64"
+  }
+
+  static var fileName: String {
+    return "\(baseName)-\(hostArchitecture)"
+  }
+
+  /// The binary to launch, or the path that should have been there when it is missing, so
+  /// the startup error names the exact artifact.
+  static func url(in resourcesURL: URL) -> URL {
+    let native = resourcesURL.appendingPathComponent(fileName)
+    if FileManager.default.fileExists(atPath: native.path) {
+      return native
+    }
+    // A bundle with only the other architecture's build should fail on the missing native one.
+    let other = resourcesURL.appendingPathComponent("\(baseName)-\(hostArchitecture == "arm64" ? "x86_64" : "arm64")")
+    let legacy = resourcesURL.appendingPathComponent(baseName)
+    if !FileManager.default.fileExists(atPath: other.path), FileManager.default.fileExists(atPath: legacy.path) {
+      return legacy
+    }
+    return native
+  }
+}
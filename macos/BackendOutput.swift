@@ -0,0 +1,69 @@
+import Foundation
+
+/// Captures the backend's stdout/stderr (still echoed to ours) and keeps the tail, so a
+/// failed start can be explained with a specific cause instead of a generic message.
+final class BackendOutput {
+  /// Known failure signatures, checked newest line first.
+  private static let signatures: [(needle: String, code: StartupError.Code)] = [
+    ("address already in use", .portInUse),
+    ("database is locked", .databaseLocked),
+    ("sqlite_busy", .databaseLocked),
+    ("file is not a database", .databaseCorrupt),
+    ("database disk image is malformed", .databaseCorrupt),
+    ("no space left on device", .diskFull),
+    ("read-only file system", .permissionDenied),
+    ("permission denied", .permissionDenied),
+    ("operation not permitted", .permissionDenied),
+  ]
+  private static let maxLines = 200
+
+  private let queue = DispatchQueue(label: "com.investlog.backend-output")
+  private var lines: [String] = []
+  private var partial: [FileHandle: String] = [:]
+
+  func attach(to process: Process) {
+    let stdoutPipe = Pipe()
+    let stderrPipe = Pipe()
+    process.standardOutput = stdoutPipe
+    process.standardError = stderrPipe
+    watch(stdoutPipe.fileHandleForReading, echo: FileHandle.standardOutput)
+    watch(stderrPipe.fileHandleForReading, echo: FileHandle.standardError)
+  }
+
+  /// The first known signature in the captured output, with the line that matched.
+  func classify() -> (code: StartupError.Code, line: String)? {
+    let snapshot = queue.sync { lines }
+    for line in snapshot.reversed() {
+      let lowered = line.lowercased()
+      if let match = BackendOutput.signatures.first(where: { lowered.contains($0.needle) }) {
+        return (match.code, ShellLog.redact(line))
+      }
+    }
+    return nil
+  }
+
+  private func watch(_ handle: FileHandle, echo: FileHandle) {
+    handle.readabilityHandler = { [weak self] readable in
+      let data = readable.availableData
+      guard !data.isEmpty else {
+        // EOF: the process is gone; stop polling the closed pipe.
+        readable.readabilityHandler = nil
+        return
+      }
+      echo.write(data)
+      self?.append(data, from: readable)
+    }
+  }
+
+  private func append(_ data: Data, from handle: FileHandle) {
+    queue.async {
+      let text = (self.partial[handle] ?? "") + String(decoding: data, as: UTF8.self)
+      var pieces = text.components(separatedBy: "\n")
+      self.partial[handle] = pieces.removeLast()
+      self.lines.append(contentsOf: pieces.filter { !$0.isEmpty })
+      if self.lines.count > BackendOutput.maxLines {
+        self.lines.removeFirst(self.lines.count - BackendOutput.maxLines)
+      }
+    }
+  }
+}
@@ -0,0 +1,75 @@
+import Foundation
+import Security
+
+/// Optional HTTPS between the shell and the backend on loopback. The backend generates a
+/// self-signed certificate in `directory` (`--tls-dir`); the shell trusts exactly that
+/// certificate and nothing else, so other local users can neither read nor impersonate it.
+enum BackendTLS {
+  static var isEnabled: Bool {
+    get { return UserDefaults.standard.bool(forKey: "BackendTLS") }
+    set { UserDefaults.standard.set(newValue, forKey: "BackendTLS") }
+  }
+
+  static var scheme: String {
+    return isEnabled ? "https" : "http"
+  }
+
+  static let directory = ShellLog.directory
+    .deletingLastPathComponent()
+    .appendingPathComponent("tls", isDirectory: true)
+
+  /// Session for every shell request to the backend; pins the certificate when TLS is on.
+  /// It never goes through a proxy: a PAC file or a proxy without loopback exceptions would
+  /// otherwise send health checks for 127.0.0.1 to the proxy.
+  static let session: URLSession = {
+    let configuration = URLSessionConfiguration.ephemeral
+    configuration.connectionProxyDictionary = [:]
+    return URLSession(configuration: configuration, delegate: PinningDelegate(), delegateQueue: nil)
+  }()
+
+  /// Accepts a server-trust challenge only if the leaf certificate is the one on disk.
+  static func evaluate(
+    _ challenge: URLAuthenticationChallenge,
+    completionHandler: @escaping (URLSession.AuthChallengeDisposition, URLCredential?) -> Void
+  ) {
+    guard challenge.protectionSpace.authenticationMethod == NSURLAuthenticationMethodServerTrust else {
+      completionHandler(.performDefaultHandling, nil)
+      return
+    }
+    guard let trust = challenge.protectionSpace.serverTrust,
+          challenge.protectionSpace.host == "127.0.0.1",
+          let pinned = pinnedCertificateData(),
+          let leaf = leafCertificateData(trust),
+          leaf == pinned else {
+      ShellLog.warn("rejected backend certificate", ["host": challenge.protectionSpace.host])
+      completionHandler(.cancelAuthenticationChallenge, nil)
+      return
+    }
+    completionHandler(.useCredential, URLCredential(trust: trust))
+  }
+
+  /// DER bytes of `cert.pem`, read fresh so a regenerated certificate is picked up.
+  private static func pinnedCertificateData() -> Data? {
+    guard let pem = try? String(contentsOf: directory.appendingPathComponent("cert.pem"), encoding: .utf8) else { return nil }
+    let base64 = pem
+      .components(separatedBy: .newlines)
+      .filter { !$0.hasPrefix("-----") }
+      .joined()
+    return Data(base64Encoded: base64)
+  }
+
+  private static func leafCertificateData(_ trust: SecTrust) -> Data? {
+    guard let leaf = (SecTrustCopyCertificateChain(trust) as? [SecCertificate])?.first else { return nil }
+    return SecCertificateCopyData(leaf) as Data
+  }
+
+  private final class PinningDelegate: NSObject, URLSessionDelegate {
+    func urlSession(
+      _ session: URLSession,
+      didReceive challenge: URLAuthenticationChallenge,
+      completionHandler: @escaping (URLSession.AuthChallengeDisposition, URLCredential?) -> Void
+    ) {
+      BackendTLS.evaluate(challenge, completionHandler: completionHandler)
+    }
+  }
+}
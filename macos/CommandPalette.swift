@@ -0,0 +1,195 @@
+import AppKit
+
+/// Spotlight-style list of shell actions with fuzzy search.
+final class CommandPalette: NSObject, NSTableViewDataSource, NSTableViewDelegate, NSSearchFieldDelegate, NSWindowDelegate {
+  struct Command {
+    let title: String
+    let perform: () -> Void
+  }
+
+  /// Borderless panels refuse key status by default, which would break typing.
+  private final class KeyablePanel: NSPanel {
+    override var canBecomeKey: Bool { return true }
+  }
+
+  private let panel: KeyablePanel
+  private let searchField = NSSearchField()
+  private let tableView = NSTableView()
+  private var commands: [Command] = []
+  private var filtered: [Command] = []
+
+  var commandsProvider: (() -> [Command])?
+
+  override init() {
+    panel = KeyablePanel(
+      contentRect: NSRect(x: 0, y: 0, width: 520, height: 320),
+      styleMask: [.borderless, .nonactivatingPanel],
+      backing: .buffered,
+      defer: false
+    )
+    super.init()
+
+    panel.level = .floating
+    panel.isReleasedWhenClosed = false
+    panel.hasShadow = true
+    panel.backgroundColor = .clear
+    panel.delegate = self
+
+    let background = NSVisualEffectView()
+    background.material = .popover
+    background.state = .active
+    background.wantsLayer = true
+    background.layer?.cornerRadius = 12
+    background.layer?.masksToBounds = true
+    panel.contentView = background
+
+    searchField.placeholderString = "Type a command…"
+    searchField.font = .systemFont(ofSize: 18)
+    searchField.focusRingType = .none
+    searchField.delegate = self
+    searchField.translatesAutoresizingMaskIntoConstraints = false
+
+    let column = NSTableColumn(identifier: NSUserInterfaceItemIdentifier("title"))
+    tableView.addTableColumn(column)
+    tableView.headerView = nil
+    tableView.rowHeight = 26
+    tableView.backgroundColor = .clear
+    tableView.dataSource = self
+    tableView.delegate = self
+    tableView.target = self
+    tableView.doubleAction = #selector(runSelected)
+
+    let scrollView = NSScrollView()
+    scrollView.documentView = tableView
+    scrollView.hasVerticalScroller = true
+    scrollView.drawsBackground = false
+    scrollView.translatesAutoresizingMaskIntoConstraints = false
+
+    background.addSubview(searchField)
+    background.addSubview(scrollView)
+    NSLayoutConstraint.activate([
+      searchField.topAnchor.constraint(equalTo: background.topAnchor, constant: 14),
+      searchField.leadingAnchor.constraint(equalTo: background.leadingAnchor, constant: 14),
+      searchField.trailingAnchor.constraint(equalTo: background.trailingAnchor, constant: -14),
+      scrollView.topAnchor.constraint(equalTo: searchField.bottomAnchor, constant: 10),
+      scrollView.leadingAnchor.constraint(equalTo: background.leadingAnchor, constant: 6),
+      scrollView.trailingAnchor.constraint(equalTo: background.trailingAnchor, constant: -6),
+      scrollView.bottomAnchor.constraint(equalTo: background.bottomAnchor, constant: -8),
+    ])
+  }
+
+  func toggle(over parent: NSWindow?) {
+    if panel.isVisible {
+      dismiss()
+      return
+    }
+    commands = commandsProvider?() ?? []
+    searchField.stringValue = ""
+    applyFilter()
+
+    if let parent = parent, parent.isVisible {
+      let frame = parent.frame
+      panel.setFrameOrigin(NSPoint(x: frame.midX - panel.frame.width / 2, y: frame.maxY - panel.frame.height - 120))
+    } else {
+      panel.center()
+    }
+    panel.makeKeyAndOrderFront(nil)
+    NSApp.activate(ignoringOtherApps: true)
+    panel.makeFirstResponder(searchField)
+  }
+
+  func windowDidResignKey(_ notification: Notification) {
+    dismiss()
+  }
+
+  func controlTextDidChange(_ obj: Notification) {
+    applyFilter()
+  }
+
+  func control(_ control: NSControl, textView: NSTextView, doCommandBy commandSelector: Selector) -> Bool {
+    switch commandSelector {
+    case #selector(NSResponder.moveDown(_:)):
+      moveSelection(by: 1)
+    case #selector(NSResponder.moveUp(_:)):
+      moveSelection(by: -1)
+    case #selector(NSResponder.insertNewline(_:)):
+      runSelected()
+    case #selector(NSResponder.cancelOperation(_:)):
+      dismiss()
+    default:
+      return false
+    }
+    return true
+  }
+
+  func numberOfRows(in tableView: NSTableView) -> Int {
+    return filtered.count
+  }
+
+  func tableView(_ tableView: NSTableView, viewFor tableColumn: NSTableColumn?, row: Int) -> NSView? {
+    let field = NSTextField(labelWithString: filtered[row].title)
+    field.font = .systemFont(ofSize: 14)
+    field.lineBreakMode = .byTruncatingTail
+    return field
+  }
+
+  private func applyFilter() {
+    let query = searchField.stringValue
+    if query.isEmpty {
+      filtered = commands
+    } else {
+      filtered = commands
+        .compactMap { command in CommandPalette.score(query, command.title).map { (command, $0) } }
+        .sorted { $0.1 > $1.1 }
+        .map { $0.0 }
+    }
+    tableView.reloadData()
+    if !filtered.isEmpty {
+      tableView.selectRowIndexes(IndexSet(integer: 0), byExtendingSelection: false)
+    }
+  }
+
+  private func moveSelection(by delta: Int) {
+    guard !filtered.isEmpty else { return }
+    let next = min(max(tableView.selectedRow + delta, 0), filtered.count - 1)
+    tableView.selectRowIndexes(IndexSet(integer: next), byExtendingSelection: false)
+    tableView.scrollRowToVisible(next)
+  }
+
+  @objc private func runSelected() {
+    let row = tableView.selectedRow
+    guard row >= 0, row < filtered.count else { return }
+    let command = filtered[row]
+    dismiss()
+    // Run after the panel has gone so responder-chain actions reach the previous key window.
+    DispatchQueue.main.async { command.perform() }
+  }
+
+  private func dismiss() {
+    panel.orderOut(nil)
+  }
+
+  /// Subsequence match; consecutive hits and word starts score higher.
+  static func score(_ query: String, _ text: String) -> Int? {
+    let needle = Array(query.lowercased().filter { !$0.isWhitespace })
+    let haystack = Array(text.lowercased())
+    guard !needle.isEmpty else { return 0 }
+
+    var score = 0
+    var index = 0
+    var previousMatch = -2
+    for (position, character) in haystack.enumerated() where index < needle.count {
+      guard character == needle[index] else { continue }
+      score += 1
+      if position == previousMatch + 1 {
+        score += 3
+      }
+      if position == 0 || haystack[position - 1] == " " {
+        score += 2
+      }
+      previousMatch = position
+      index += 1
+    }
+    return index == needle.count ? score : nil
+  }
+}
@@ -0,0 +1,121 @@
+import AppKit
+import Darwin
+
+/// Writes a report when the shell dies from an uncaught exception or a fatal signal,
+/// and offers it to the user on the next launch.
+enum CrashReporter {
+  static let directory = ShellLog.directory
+    .deletingLastPathComponent()
+    .appendingPathComponent("crashes", isDirectory: true)
+
+  private static let logTailMarker = "--- Recent shell log ---"
+  private static let lastSeenKey = "LastSeenCrashReport"
+
+  /// Prepared up front: a signal handler may only make async-signal-safe calls,
+  /// so it can't format strings or allocate.
+  private static var signalReportPath: UnsafeMutablePointer<CChar>?
+  private static var signalReportHeader: UnsafeMutablePointer<CChar>?
+  private static let signalFrameCapacity: Int32 = 64
+  private static let signalFrames = UnsafeMutablePointer<UnsafeMutableRawPointer?>.allocate(capacity: 64)
+  private static let fatalSignals: [Int32] = [SIGABRT, SIGBUS, SIGFPE, SIGILL, SIGSEGV, SIGTRAP]
+
+  static func install() {
+    try? FileManager.default.createDirectory(at: directory, withIntermediateDirectories: true)
+
+    NSSetUncaughtExceptionHandler { exception in
+      var report = CrashReporter.header()
+      report += "Exception: \(exception.name.rawValue)\n"
+      report += "Reason: \(exception.reason ?? "unknown")\n\n"
+      report += exception.callStackSymbols.joined(separator: "\n")
+      report += "\n\n\(CrashReporter.logTailMarker)\n\(CrashReporter.shellLogTail())\n"
+      let url = CrashReporter.directory.appendingPathComponent("crash-\(CrashReporter.timestamp()).txt")
+      try? ShellLog.redact(report).write(to: url, atomically: true, encoding: .utf8)
+    }
+
+    let signalURL = directory.appendingPathComponent("crash-\(timestamp())-signal.txt")
+    signalReportPath = strdup(signalURL.path)
+    signalReportHeader = strdup(header() + "Fatal signal; backtrace:\n")
+    _ = signalFrames
+    for signalNumber in fatalSignals {
+      signal(signalNumber) { received in
+        if let path = CrashReporter.signalReportPath {
+          let fd = open(path, O_WRONLY | O_CREAT | O_TRUNC, 0o644)
+          if fd >= 0 {
+            if let header = CrashReporter.signalReportHeader {
+              _ = write(fd, header, strlen(header))
+            }
+            let count = backtrace(CrashReporter.signalFrames, CrashReporter.signalFrameCapacity)
+            backtrace_symbols_fd(CrashReporter.signalFrames, count, fd)
+            close(fd)
+          }
+        }
+        // Restore the default action so the system still records the crash.
+        signal(received, SIG_DFL)
+        raise(received)
+      }
+    }
+  }
+
+  /// Offers the newest report written since the last one the user saw.
+  static func offerPreviousReport(copyDiagnostics: @escaping () -> Void) {
+    let names = (try? FileManager.default.contentsOfDirectory(atPath: directory.path)) ?? []
+    guard let newest = names.filter({ $0.hasPrefix("crash-") && $0.hasSuffix(".txt") }).sorted().last,
+          newest != UserDefaults.standard.string(forKey: lastSeenKey) else { return }
+    UserDefaults.standard.set(newest, forKey: lastSeenKey)
+
+    let url = directory.appendingPathComponent(newest)
+    // Signal reports couldn't read the log while crashing; add it now, before this
+    // launch has written much of its own.
+    if let contents = try? String(contentsOf: url, encoding: .utf8), !contents.contains(logTailMarker) {
+      let report = contents + "\n\(logTailMarker)\n\(shellLogTail())\n"
+      try? ShellLog.redact(report).write(to: url, atomically: true, encoding: .utf8)
+    }
+    ShellLog.warn("previous session crashed", ["report": url.path])
+
+    let alert = NSAlert()
+    alert.messageText = "Invest Log quit unexpectedly"
+    alert.informativeText = "A crash report was saved. You can attach it when reporting the problem."
+    alert.addButton(withTitle: "Show Report")
+    alert.addButton(withTitle: "Copy Diagnostics")
+    alert.addButton(withTitle: "Ignore")
+    switch alert.runModal() {
+    case .alertFirstButtonReturn:
+      NSWorkspace.shared.activateFileViewerSelecting([url])
+    case .alertSecondButtonReturn:
+      copyDiagnostics()
+    default:
+      break
+    }
+  }
+
+  private static func header() -> String {
+    let version = Bundle.main.object(forInfoDictionaryKey: "CFBundleShortVersionString") as? String ?? "unknown"
+    var info = utsname()
+    uname(&info)
+    let machine = withUnsafeBytes(of: info.machine) { bytes in
+      String(decoding: bytes.prefix { $0 != 0 }, as: UTF8.self)
+    }
+    return """
+      Invest Log \(version)
+      macOS \(ProcessInfo.processInfo.operatingSystemVersionString) (\(machine))
+      Date: \(Date())
+
+      """
+  }
+
+  private static func timestamp() -> String {
+    let formatter = DateFormatter()
+    formatter.locale = Locale(identifier: "en_US_POSIX")
+    formatter.dateFormat = "yyyyMMdd-HHmmss"
+    return formatter.string(from: Date())
+  }
+
+  private static func shellLogTail(lines: Int = 50) -> String {
+    let names = (try? FileManager.default.contentsOfDirectory(atPath: ShellLog.directory.path)) ?? []
+    guard let latest = names.filter({ $0.hasPrefix("shell-") && $0.hasSuffix(".log") }).sorted().last,
+          let contents = try? String(contentsOf: ShellLog.directory.appendingPathComponent(latest), encoding: .utf8) else {
+      return "(no shell log)"
+    }
+    return contents.split(separator: "\n").suffix(lines).joined(separator: "\n")
+  }
+}
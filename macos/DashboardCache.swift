@@ -0,0 +1,81 @@
+import Foundation
+
+/// The last portfolio totals the shell saw, shown read-only on the loader with a
+/// "refreshing" note while the backend starts, so a launch opens on numbers rather than
+/// only a spinner. Saved whenever the menu bar summary refreshes, which also covers the
+/// state right before quitting. Neither saved nor shown while the app lock or the privacy
+/// shield is on, since the loader appears before either can hide it.
+enum DashboardCache {
+  private static let fileURL = ShellLog.directory
+    .deletingLastPathComponent()
+    .appendingPathComponent("dashboard-cache.json")
+
+  private struct Snapshot: Codable {
+    struct Total: Codable {
+      let currency: String
+      let marketValue: Double
+      let pnl: Double
+
+      enum CodingKeys: String, CodingKey {
+        case currency
+        case marketValue = "market_value"
+        case pnl
+      }
+    }
+
+    let savedAt: Date
+    let totals: [Total]
+
+    enum CodingKeys: String, CodingKey {
+      case savedAt = "saved_at"
+      case totals
+    }
+  }
+
+  private static var isAllowed: Bool {
+    return !AppLock.isEnabled && !PrivacyShield.isEnabled
+  }
+
+  static func save(_ summary: PortfolioSummary) {
+    guard isAllowed, !summary.totals.isEmpty else { return }
+    let snapshot = Snapshot(
+      savedAt: Date(),
+      totals: summary.totals.map { Snapshot.Total(currency: $0.currency, marketValue: $0.marketValue, pnl: $0.pnl) }
+    )
+    let encoder = JSONEncoder()
+    encoder.dateEncodingStrategy = .iso8601
+    guard let data = try? encoder.encode(snapshot) else { return }
+    try? FileManager.default.createDirectory(at: fileURL.deletingLastPathComponent(), withIntermediateDirectories: true)
+    do {
+      try data.write(to: fileURL, options: .atomic)
+      try FileManager.default.setAttributes([.posixPermissions: 0o600], ofItemAtPath: fileURL.path)
+    } catch {
+      ShellLog.warn("dashboard cache not saved", ["error": error.localizedDescription])
+    }
+  }
+
+  static func clear() {
+    try? FileManager.default.removeItem(at: fileURL)
+  }
+
+  /// Formatted for the loader: `{ savedAt: <ms since 1970>, totals: [{ currency, value, pnl }] }`.
+  static func loaderPayload() -> [String: Any]? {
+    guard isAllowed, let data = try? Data(contentsOf: fileURL) else { return nil }
+    let decoder = JSONDecoder()
+    decoder.dateDecodingStrategy = .iso8601
+    guard let snapshot = try? decoder.decode(Snapshot.self, from: data), !snapshot.totals.isEmpty else {
+      return nil
+    }
+    return [
+      "savedAt": Int(snapshot.savedAt.timeIntervalSince1970 * 1000),
+      "totals": snapshot.totals.map { total -> [String: Any] in
+        [
+          "currency": total.currency,
+          "value": PortfolioSummary.formatAmount(total.marketValue, currency: total.currency),
+          "pnl": PortfolioSummary.formatAmount(total.pnl, currency: ""),
+          "gain": total.pnl >= 0,
+        ]
+      },
+    ]
+  }
+}
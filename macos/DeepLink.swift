@@ -0,0 +1,30 @@
+import Foundation
+
+/// Maps `investlog://` URLs onto SPA hash routes, e.g.
+/// `investlog://symbol-analysis?symbol=AAPL&currency=USD` → `symbol-analysis?symbol=AAPL&currency=USD`.
+enum DeepLink {
+  static let scheme = "investlog"
+
+  private static let routeCharacters = CharacterSet(charactersIn: "abcdefghijklmnopqrstuvwxyz0123456789-/")
+
+  static func route(from url: URL) -> String? {
+    guard url.scheme?.lowercased() == scheme,
+          let components = URLComponents(url: url, resolvingAgainstBaseURL: false) else {
+      return nil
+    }
+    let raw = ((components.host ?? "") + components.path).lowercased()
+    let route = raw.trimmingCharacters(in: CharacterSet(charactersIn: "/"))
+    guard !route.isEmpty, route.unicodeScalars.allSatisfy({ routeCharacters.contains($0) }) else {
+      return nil
+    }
+
+    // Re-encode the query so nothing from the link reaches the page unescaped.
+    var query = URLComponents()
+    query.queryItems = components.queryItems
+    guard let encoded = query.percentEncodedQuery?.replacingOccurrences(of: "'", with: "%27"),
+          !encoded.isEmpty else {
+      return route
+    }
+    return "\(route)?\(encoded)"
+  }
+}
@@ -0,0 +1,48 @@
+import Foundation
+
+/// Zips a summary, the latest shell and backend logs and the newest crash report into
+/// one file the user can attach to a bug report. Everything is redacted like the logs.
+enum DiagnosticsBundle {
+  static let directory = ShellLog.directory
+    .deletingLastPathComponent()
+    .appendingPathComponent("diagnostics", isDirectory: true)
+
+  /// Blocking; call off the main thread. Returns the zip, or the folder if zipping failed.
+  static func create(summary: String, backendLogsDirectory: URL?) -> URL? {
+    let formatter = DateFormatter()
+    formatter.locale = Locale(identifier: "en_US_POSIX")
+    formatter.dateFormat = "yyyyMMdd-HHmmss"
+    let name = "InvestLog-Diagnostics-\(formatter.string(from: Date()))"
+    let folder = directory.appendingPathComponent(name, isDirectory: true)
+    let fileManager = FileManager.default
+    do {
+      try fileManager.createDirectory(at: folder, withIntermediateDirectories: true)
+      try ShellLog.redact(summary).write(to: folder.appendingPathComponent("summary.txt"), atomically: true, encoding: .utf8)
+    } catch {
+      ShellLog.error("diagnostics bundle failed", ["err": error.localizedDescription])
+      return nil
+    }
+
+    copyLatest(prefix: "shell-", suffix: ".log", from: ShellLog.directory, into: folder)
+    copyLatest(prefix: "app-", suffix: ".log", from: backendLogsDirectory ?? ShellLog.directory, into: folder)
+    copyLatest(prefix: "crash-", suffix: ".txt", from: CrashReporter.directory, into: folder)
+
+    let zipURL = directory.appendingPathComponent("\(name).zip")
+    let ditto = Process()
+    ditto.executableURL = URL(fileURLWithPath: "/usr/bin/ditto")
+    ditto.arguments = ["-c", "-k", "--keepParent", folder.path, zipURL.path]
+    guard (try? ditto.run()) != nil else { return folder }
+    ditto.waitUntilExit()
+    guard ditto.terminationStatus == 0 else { return folder }
+    try? fileManager.removeItem(at: folder)
+    ShellLog.info("diagnostics bundle created", ["path": zipURL.path])
+    return zipURL
+  }
+
+  private static func copyLatest(prefix: String, suffix: String, from source: URL, into folder: URL) {
+    let names = (try? FileManager.default.contentsOfDirectory(atPath: source.path)) ?? []
+    guard let latest = names.filter({ $0.hasPrefix(prefix) && $0.hasSuffix(suffix) }).sorted().last,
+          let contents = try? String(contentsOf: source.appendingPathComponent(latest), encoding: .utf8) else { return }
+    try? ShellLog.redact(contents).write(to: folder.appendingPathComponent(latest), atomically: true, encoding: .utf8)
+  }
+}
@@ -0,0 +1,47 @@
+import AppKit
+
+/// Warns once per volume when the data directory lives on a disk without FileVault or
+/// other volume encryption, since the database holds the full portfolio in plain SQLite.
+enum DiskEncryptionCheck {
+  private static let warnedKeyPrefix = "UnencryptedVolumeWarning."
+  private static let fileVaultSettingsURL = URL(string: "x-apple.systempreferences:com.apple.preference.security?FileVault")!
+
+  static func warnIfUnencrypted(dataDir: URL) {
+    let keys: Set<URLResourceKey> = [.volumeIsEncryptedKey, .volumeUUIDStringKey, .volumeNameKey, .volumeIsInternalKey]
+    guard let values = try? dataDir.resourceValues(forKeys: keys),
+          let encrypted = values.volumeIsEncrypted else { return }
+    guard !encrypted else { return }
+
+    let volumeID = values.volumeUUIDString ?? dataDir.path
+    let warnedKey = warnedKeyPrefix + volumeID
+    guard !UserDefaults.standard.bool(forKey: warnedKey) else { return }
+    UserDefaults.standard.set(true, forKey: warnedKey)
+
+    let volumeName = values.volumeName ?? "this disk"
+    ShellLog.warn("data volume is not encrypted", ["volume": volumeName, "data_dir": dataDir.path])
+
+    let alert = NSAlert()
+    alert.messageText = "Your Invest Log data is on an unencrypted disk"
+    alert.alertStyle = .warning
+    if values.volumeIsInternal == false {
+      alert.informativeText = """
+        The data folder is on "\(volumeName)", which is not encrypted. Anyone with the disk can \
+        read your holdings and transactions. Encrypt it from Finder (Control-click the disk, then \
+        choose Encrypt). This warning won't be shown again.
+        """
+      alert.addButton(withTitle: "OK")
+      alert.runModal()
+      return
+    }
+    alert.informativeText = """
+      FileVault is off for "\(volumeName)". Anyone with access to this Mac's disk can read your \
+      holdings and transactions. Turn on FileVault in System Settings > Privacy & Security to \
+      encrypt it. This warning won't be shown again.
+      """
+    alert.addButton(withTitle: "Open FileVault Settings")
+    alert.addButton(withTitle: "Not Now")
+    if alert.runModal() == .alertFirstButtonReturn {
+      NSWorkspace.shared.open(fileVaultSettingsURL)
+    }
+  }
+}
@@ -0,0 +1,95 @@
+import Carbon
+import Foundation
+
+/// System-wide keyboard shortcut backed by Carbon's RegisterEventHotKey.
+final class GlobalHotKey {
+  private static let signature: OSType = 0x494E_564C  // "INVL"
+  private static var registry: [UInt32: GlobalHotKey] = [:]
+  private static var nextID: UInt32 = 1
+  private static var handlerInstalled = false
+
+  private let id: UInt32
+  private var hotKeyRef: EventHotKeyRef?
+  private let action: () -> Void
+
+  /// Parses shortcuts such as "cmd+shift+i" and registers them; returns nil when
+  /// the string is empty, unparseable, or already taken by another app.
+  init?(shortcut: String, action: @escaping () -> Void) {
+    guard let (keyCode, modifiers) = GlobalHotKey.parse(shortcut) else { return nil }
+    GlobalHotKey.installHandlerIfNeeded()
+
+    self.id = GlobalHotKey.nextID
+    self.action = action
+    GlobalHotKey.nextID += 1
+
+    let hotKeyID = EventHotKeyID(signature: GlobalHotKey.signature, id: id)
+    let status = RegisterEventHotKey(keyCode, modifiers, hotKeyID, GetApplicationEventTarget(), 0, &hotKeyRef)
+    guard status == noErr else { return nil }
+    GlobalHotKey.registry[id] = self
+  }
+
+  func unregister() {
+    if let hotKeyRef = hotKeyRef {
+      UnregisterEventHotKey(hotKeyRef)
+    }
+    hotKeyRef = nil
+    GlobalHotKey.registry.removeValue(forKey: id)
+  }
+
+  private static func installHandlerIfNeeded() {
+    guard !handlerInstalled else { return }
+    handlerInstalled = true
+
+    var eventType = EventTypeSpec(eventClass: OSType(kEventClassKeyboard), eventKind: UInt32(kEventHotKeyPressed))
+    InstallEventHandler(GetApplicationEventTarget(), { _, event, _ in
+      var hotKeyID = EventHotKeyID()
+      let status = GetEventParameter(
+        event,
+        EventParamName(kEventParamDirectObject),
+        EventParamType(typeEventHotKeyID),
+        nil,
+        MemoryLayout<EventHotKeyID>.size,
+        nil,
+        &hotKeyID
+      )
+      guard status == noErr, let hotKey = GlobalHotKey.registry[hotKeyID.id] else {
+        return OSStatus(eventNotHandledErr)
+      }
+      DispatchQueue.main.async { hotKey.action() }
+      return noErr
+    }, 1, &eventType, nil, nil)
+  }
+
+  private static let keyCodes: [String: Int] = [
+    "a": kVK_ANSI_A, "b": kVK_ANSI_B, "c": kVK_ANSI_C, "d": kVK_ANSI_D, "e": kVK_ANSI_E,
+    "f": kVK_ANSI_F, "g": kVK_ANSI_G, "h": kVK_ANSI_H, "i": kVK_ANSI_I, "j": kVK_ANSI_J,
+    "k": kVK_ANSI_K, "l": kVK_ANSI_L, "m": kVK_ANSI_M, "n": kVK_ANSI_N, "o": kVK_ANSI_O,
+    "p": kVK_ANSI_P, "q": kVK_ANSI_Q, "r": kVK_ANSI_R, "s": kVK_ANSI_S, "t": kVK_ANSI_T,
+    "u": kVK_ANSI_U, "v": kVK_ANSI_V, "w": kVK_ANSI_W, "x": kVK_ANSI_X, "y": kVK_ANSI_Y,
+    "z": kVK_ANSI_Z,
+    "0": kVK_ANSI_0, "1": kVK_ANSI_1, "2": kVK_ANSI_2, "3": kVK_ANSI_3, "4": kVK_ANSI_4,
+    "5": kVK_ANSI_5, "6": kVK_ANSI_6, "7": kVK_ANSI_7, "8": kVK_ANSI_8, "9": kVK_ANSI_9,
+    "space": kVK_Space,
+  ]
+
+  private static func parse(_ shortcut: String) -> (UInt32, UInt32)? {
+    let parts = shortcut.lowercased()
+      .split(separator: "+")
+      .map { $0.trimmingCharacters(in: .whitespaces) }
+    guard let keyName = parts.last, let keyCode = keyCodes[keyName] else { return nil }
+
+    var modifiers = 0
+    for part in parts.dropLast() {
+      switch part {
+      case "cmd", "command": modifiers |= cmdKey
+      case "shift": modifiers |= shiftKey
+      case "opt", "option", "alt": modifiers |= optionKey
+      case "ctrl", "control": modifiers |= controlKey
+      default: return nil
+      }
+    }
+    // A bare key would swallow normal typing system-wide.
+    guard modifiers != 0 else { return nil }
+    return (UInt32(keyCode), UInt32(modifiers))
+  }
+}
@@ -0,0 +1,159 @@
+import AppKit
+
+/// "App Health" window: shell and backend process state plus storage figures from the backend.
+final class HealthWindowController: NSObject, NSWindowDelegate {
+  struct ShellState {
+    let version: String
+    let backendRunning: Bool
+    let backendPID: Int32?
+    let backendStartedAt: Date?
+    let baseURL: URL
+  }
+
+  private struct StorageStats: Decodable {
+    struct FileStat: Decodable {
+      let path: String
+      let exists: Bool
+      let size: Int64
+    }
+
+    let dataDir: String
+    let db: FileStat
+    let wal: FileStat
+    let logsSize: Int64
+    let totalSize: Int64
+
+    enum CodingKeys: String, CodingKey {
+      case dataDir = "data_dir"
+      case db
+      case wal
+      case logsSize = "logs_size"
+      case totalSize = "total_size"
+    }
+  }
+
+  private static let rows = [
+    "Shell version", "Backend", "Process ID", "Address", "Uptime", "Health check",
+    "Database", "Database size", "Data folder size", "Logs size",
+  ]
+
+  private let window: NSWindow
+  private var valueFields: [String: NSTextField] = [:]
+  private var timer: Timer?
+
+  var stateProvider: (() -> ShellState)?
+
+  override init() {
+    window = NSWindow(
+      contentRect: NSRect(x: 0, y: 0, width: 520, height: 320),
+      styleMask: [.titled, .closable, .miniaturizable],
+      backing: .buffered,
+      defer: false
+    )
+    super.init()
+
+    window.title = "App Health"
+    window.isReleasedWhenClosed = false
+    window.setFrameAutosaveName("AppHealth")
+    window.delegate = self
+
+    let grid = NSGridView(numberOfColumns: 2, rows: 0)
+    grid.rowSpacing = 6
+    grid.columnSpacing = 12
+    for title in HealthWindowController.rows {
+      let label = NSTextField(labelWithString: title)
+      label.textColor = .secondaryLabelColor
+      let value = NSTextField(labelWithString: "—")
+      value.isSelectable = true
+      value.lineBreakMode = .byTruncatingMiddle
+      value.widthAnchor.constraint(lessThanOrEqualToConstant: 360).isActive = true
+      valueFields[title] = value
+      grid.addRow(with: [label, value])
+    }
+    grid.column(at: 0).xPlacement = .trailing
+
+    let refreshButton = NSButton(title: "Refresh", target: self, action: #selector(refresh(_:)))
+    let content = NSStackView(views: [grid, refreshButton])
+    content.orientation = .vertical
+    content.alignment = .leading
+    content.spacing = 16
+    content.edgeInsets = NSEdgeInsets(top: 20, left: 20, bottom: 20, right: 20)
+    window.contentView = content
+  }
+
+  func show() {
+    if window.frame.origin == .zero {
+      window.center()
+    }
+    refresh(nil)
+    window.makeKeyAndOrderFront(nil)
+    NSApp.activate(ignoringOtherApps: true)
+    timer?.invalidate()
+    timer = Timer.scheduledTimer(withTimeInterval: 5.0, repeats: true) { [weak self] _ in
+      self?.refresh(nil)
+    }
+  }
+
+  func windowWillClose(_ notification: Notification) {
+    timer?.invalidate()
+    timer = nil
+  }
+
+  @objc private func refresh(_ sender: Any?) {
+    guard let state = stateProvider?() else { return }
+    set("Shell version", state.version)
+    set("Backend", state.backendRunning ? "Running" : "Stopped")
+    set("Process ID", state.backendPID.map { "\($0)" } ?? "—")
+    set("Address", state.baseURL.absoluteString)
+    set("Uptime", state.backendStartedAt.map { HealthWindowController.formatDuration(Date().timeIntervalSince($0)) } ?? "—")
+
+    let started = Date()
+    var healthRequest = URLRequest(url: state.baseURL.appendingPathComponent("api/health"))
+    healthRequest.timeoutInterval = 2.0
+    BackendTLS.session.dataTask(with: healthRequest) { [weak self] _, response, error in
+      let ok = (response as? HTTPURLResponse)?.statusCode == 200
+      let latency = Int(Date().timeIntervalSince(started) * 1000)
+      DispatchQueue.main.async {
+        self?.set("Health check", ok ? "OK (\(latency) ms)" : "Failed: \(error?.localizedDescription ?? "no response")")
+      }
+    }.resume()
+
+    var statsRequest = URLRequest(url: state.baseURL.appendingPathComponent("api/storage/stats"))
+    statsRequest.timeoutInterval = 2.0
+    BackendTLS.session.dataTask(with: statsRequest) { [weak self] data, response, _ in
+      var stats: StorageStats?
+      if let http = response as? HTTPURLResponse, http.statusCode == 200, let data = data {
+        stats = try? JSONDecoder().decode(StorageStats.self, from: data)
+      }
+      DispatchQueue.main.async { self?.apply(stats) }
+    }.resume()
+  }
+
+  private func apply(_ stats: StorageStats?) {
+    guard let stats = stats else {
+      ["Database", "Database size", "Data folder size", "Logs size"].forEach { set($0, "Unavailable") }
+      return
+    }
+    set("Database", stats.db.exists ? stats.db.path : "\(stats.db.path) (missing)")
+    set("Database size", HealthWindowController.formatBytes(stats.db.size + stats.wal.size))
+    set("Data folder size", HealthWindowController.formatBytes(stats.totalSize))
+    set("Logs size", HealthWindowController.formatBytes(stats.logsSize))
+  }
+
+  private func set(_ row: String, _ value: String) {
+    valueFields[row]?.stringValue = value
+    valueFields[row]?.toolTip = value
+  }
+
+  private static func formatBytes(_ bytes: Int64) -> String {
+    return ByteCountFormatter.string(fromByteCount: bytes, countStyle: .file)
+  }
+
+  private static func formatDuration(_ interval: TimeInterval) -> String {
+    let formatter = DateComponentsFormatter()
+    formatter.allowedUnits = [.day, .hour, .minute, .second]
+    formatter.unitsStyle = .abbreviated
+    formatter.maximumUnitCount = 2
+    return formatter.string(from: interval) ?? "—"
+  }
+}
@@ -0,0 +1,95 @@
+import Foundation
+import Security
+import WebKit
+
+/// Generic-password items in the login keychain, one per credential name, so API keys and
+/// broker tokens stay out of config files and the SQLite database. `credentialsService` holds
+/// what pages may ask for; secrets only the shell uses go under a service of their own.
+enum Keychain {
+  static let credentialsService = "com.investlog.app.credentials"
+
+  static func store(_ value: String, name: String, service: String = credentialsService) -> Bool {
+    let query = baseQuery(name, service: service)
+    let attributes: [String: Any] = [kSecValueData as String: Data(value.utf8)]
+    var status = SecItemUpdate(query as CFDictionary, attributes as CFDictionary)
+    if status == errSecItemNotFound {
+      var item = query
+      item[kSecValueData as String] = Data(value.utf8)
+      item[kSecAttrAccessible as String] = kSecAttrAccessibleWhenUnlocked
+      status = SecItemAdd(item as CFDictionary, nil)
+    }
+    if status != errSecSuccess {
+      ShellLog.error("keychain store failed", ["name": name, "status": status])
+    }
+    return status == errSecSuccess
+  }
+
+  static func retrieve(name: String, service: String = credentialsService) -> String? {
+    var query = baseQuery(name, service: service)
+    query[kSecReturnData as String] = true
+    query[kSecMatchLimit as String] = kSecMatchLimitOne
+    var result: AnyObject?
+    let status = SecItemCopyMatching(query as CFDictionary, &result)
+    guard status == errSecSuccess, let data = result as? Data else {
+      if status != errSecItemNotFound {
+        ShellLog.error("keychain read failed", ["name": name, "status": status])
+      }
+      return nil
+    }
+    return String(data: data, encoding: .utf8)
+  }
+
+  @discardableResult
+  static func delete(name: String, service: String = credentialsService) -> Bool {
+    let status = SecItemDelete(baseQuery(name, service: service) as CFDictionary)
+    return status == errSecSuccess || status == errSecItemNotFound
+  }
+
+  private static func baseQuery(_ name: String, service: String) -> [String: Any] {
+    return [
+      kSecClass as String: kSecClassGenericPassword,
+      kSecAttrService as String: service,
+      kSecAttrAccount as String: name,
+    ]
+  }
+}
+
+/// `webkit.messageHandlers.credentials.postMessage({action, name, value})` from backend pages.
+/// Replies with the stored value for `retrieve`, or `true` for `store` / `delete`.
+final class CredentialsMessageHandler: NSObject, WKScriptMessageHandlerWithReply {
+  /// The only credentials pages may touch; anything else stays shell-only until listed here.
+  private static let allowedNames: Set<String> = ["ai_api_key"]
+
+  /// Same-origin check supplied by the app delegate; the loader and other origins get nothing.
+  var isAllowedOrigin: (URL) -> Bool = { _ in false }
+
+  func userContentController(
+    _ userContentController: WKUserContentController,
+    didReceive message: WKScriptMessage,
+    replyHandler: @escaping (Any?, String?) -> Void
+  ) {
+    guard let url = message.frameInfo.request.url, message.frameInfo.isMainFrame, isAllowedOrigin(url) else {
+      replyHandler(nil, "not allowed")
+      return
+    }
+    guard let body = message.body as? [String: Any],
+          let action = body["action"] as? String,
+          let name = body["name"] as? String,
+          CredentialsMessageHandler.allowedNames.contains(name) else {
+      replyHandler(nil, "invalid request")
+      return
+    }
+    switch action {
+    case "retrieve":
+      replyHandler(Keychain.retrieve(name: name) ?? "", nil)
+    case "store":
+      let value = (body["value"] as? String ?? "").trimmingCharacters(in: .whitespacesAndNewlines)
+      let ok = value.isEmpty ? Keychain.delete(name: name) : Keychain.store(value, name: name)
+      replyHandler(ok ? true : nil, ok ? nil : "keychain write failed")
+    case "delete":
+      replyHandler(Keychain.delete(name: name) ? true : nil, nil)
+    default:
+      replyHandler(nil, "unknown action")
+    }
+  }
+}
@@ -0,0 +1,75 @@
+import AppKit
+import CoreImage
+
+/// Opt-in access from other devices (Invest Log > Allow Access from Local Network). The
+/// backend then listens on every interface over HTTPS and asks browsers that aren't on this
+/// Mac for a six-digit PIN before serving them; the shell itself still uses 127.0.0.1.
+enum LANAccess {
+  static var isEnabled: Bool {
+    get { return UserDefaults.standard.bool(forKey: "LANAccess") }
+    set { UserDefaults.standard.set(newValue, forKey: "LANAccess") }
+  }
+
+  private static let pinName = "lan_pin"
+  /// Not the credentials service pages can reach through CredentialsMessageHandler.
+  private static let pinService = "com.investlog.app.lan"
+
+  /// Kept in the keychain so paired devices can re-pair after a restart with the same PIN.
+  static func pin() -> String {
+    if let stored = Keychain.retrieve(name: pinName, service: pinService), stored.count == 6 {
+      return stored
+    }
+    // Earlier builds kept the PIN where pages could read it; drop that one rather than move it.
+    Keychain.delete(name: pinName)
+    let pin = newPIN()
+    _ = Keychain.store(pin, name: pinName, service: pinService)
+    return pin
+  }
+
+  /// Turning access off forgets the PIN, so the next opt-in starts with a new one.
+  static func resetPIN() {
+    Keychain.delete(name: pinName, service: pinService)
+  }
+
+  /// IPv4 addresses of the active non-loopback interfaces, for the pairing instructions.
+  static func localAddresses() -> [String] {
+    var head: UnsafeMutablePointer<ifaddrs>?
+    guard getifaddrs(&head) == 0, let first = head else { return [] }
+    defer { freeifaddrs(head) }
+
+    var addresses: [String] = []
+    for pointer in sequence(first: first, next: { $0.pointee.ifa_next }) {
+      let flags = Int32(pointer.pointee.ifa_flags)
+      guard let address = pointer.pointee.ifa_addr,
+            address.pointee.sa_family == UInt8(AF_INET),
+            flags & IFF_UP != 0,
+            flags & IFF_LOOPBACK == 0 else { continue }
+      var host = [CChar](repeating: 0, count: Int(NI_MAXHOST))
+      guard getnameinfo(address, socklen_t(address.pointee.sa_len), &host, socklen_t(host.count), nil, 0, NI_NUMERICHOST) == 0 else { continue }
+      let ip = String(cString: host)
+      if !ip.hasPrefix("169.254."), !addresses.contains(ip) {
+        addresses.append(ip)
+      }
+    }
+    return addresses
+  }
+
+  /// Renders the pairing link as a QR code; `scale` turns each module into that many points.
+  static func qrCode(for url: URL, scale: CGFloat = 8) -> NSImage? {
+    guard let filter = CIFilter(name: "CIQRCodeGenerator") else { return nil }
+    filter.setValue(Data(url.absoluteString.utf8), forKey: "inputMessage")
+    filter.setValue("M", forKey: "inputCorrectionLevel")
+    guard let output = filter.outputImage?.transformed(by: CGAffineTransform(scaleX: scale, y: scale)) else {
+      return nil
+    }
+    let rep = NSCIImageRep(ciImage: output)
+    let image = NSImage(size: rep.size)
+    image.addRepresentation(rep)
+    return image
+  }
+
+  /// SystemRandomNumberGenerator is backed by the kernel CSPRNG on Apple platforms.
+  private static func newPIN() -> String {
+    return String(format: "%06u", UInt32.random(in: 0..<1_000_000))
+  }
+}
@@ -0,0 +1,183 @@
+import AppKit
+
+/// Read-only tail of today's backend or shell log with a minimum-level filter.
+final class LogViewerWindowController: NSObject, NSWindowDelegate {
+  enum Source: Int {
+    case backend
+    case shell
+
+    var prefix: String {
+      return self == .backend ? "app-" : "shell-"
+    }
+  }
+
+  private static let levels = ["DEBUG", "INFO", "WARN", "ERROR"]
+  private static let maxLines = 2000
+
+  private let window: NSWindow
+  private let textView = NSTextView()
+  private let sourcePopup = NSPopUpButton()
+  private let levelPopup = NSPopUpButton()
+  private let followCheckbox = NSButton(checkboxWithTitle: "Follow", target: nil, action: nil)
+  private var timer: Timer?
+  private var lines: [String] = []
+  private var fileURL: URL?
+  private var offset: UInt64 = 0
+  private var partialLine = ""
+
+  /// The backend writes into the data directory, which only the running backend knows.
+  var backendLogsDirectory: URL?
+
+  var isVisible: Bool {
+    return window.isVisible
+  }
+
+  override init() {
+    window = NSWindow(
+      contentRect: NSRect(x: 0, y: 0, width: 860, height: 520),
+      styleMask: [.titled, .closable, .miniaturizable, .resizable],
+      backing: .buffered,
+      defer: false
+    )
+    super.init()
+
+    window.title = "Invest Log — Logs"
+    window.isReleasedWhenClosed = false
+    window.setFrameAutosaveName("LogViewer")
+    window.delegate = self
+
+    sourcePopup.addItems(withTitles: ["Backend", "Shell"])
+    sourcePopup.target = self
+    sourcePopup.action = #selector(sourceChanged(_:))
+    levelPopup.addItems(withTitles: ["All Levels", "Info and Above", "Warnings and Above", "Errors Only"])
+    levelPopup.selectItem(at: 1)
+    levelPopup.target = self
+    levelPopup.action = #selector(filterChanged(_:))
+    followCheckbox.state = .on
+
+    let toolbar = NSStackView(views: [sourcePopup, levelPopup, followCheckbox])
+    toolbar.spacing = 12
+    toolbar.edgeInsets = NSEdgeInsets(top: 8, left: 12, bottom: 8, right: 12)
+
+    textView.isEditable = false
+    textView.isRichText = false
+    textView.font = .monospacedSystemFont(ofSize: 11, weight: .regular)
+    textView.autoresizingMask = [.width]
+    textView.isHorizontallyResizable = false
+    textView.textContainer?.widthTracksTextView = true
+
+    let scrollView = NSScrollView()
+    scrollView.documentView = textView
+    scrollView.hasVerticalScroller = true
+
+    let content = NSStackView(views: [toolbar, scrollView])
+    content.orientation = .vertical
+    content.alignment = .leading
+    content.spacing = 0
+    scrollView.widthAnchor.constraint(equalTo: content.widthAnchor).isActive = true
+    window.contentView = content
+  }
+
+  func show() {
+    if window.frame.origin == .zero {
+      window.center()
+    }
+    reload()
+    window.makeKeyAndOrderFront(nil)
+    NSApp.activate(ignoringOtherApps: true)
+    timer?.invalidate()
+    timer = Timer.scheduledTimer(withTimeInterval: 1.0, repeats: true) { [weak self] _ in
+      self?.readNewLines()
+    }
+  }
+
+  func windowWillClose(_ notification: Notification) {
+    timer?.invalidate()
+    timer = nil
+  }
+
+  @objc private func sourceChanged(_ sender: Any?) {
+    reload()
+  }
+
+  @objc private func filterChanged(_ sender: Any?) {
+    render()
+  }
+
+  private var source: Source {
+    return Source(rawValue: sourcePopup.indexOfSelectedItem) ?? .backend
+  }
+
+  private func reload() {
+    let directory = source == .backend ? (backendLogsDirectory ?? ShellLog.directory) : ShellLog.directory
+    fileURL = latestLog(in: directory, prefix: source.prefix)
+    offset = 0
+    partialLine = ""
+    lines = []
+    readNewLines()
+    render()
+  }
+
+  /// Log files are named `<prefix>YYYYMMDD.log`, so the lexically last one is today's.
+  private func latestLog(in directory: URL, prefix: String) -> URL? {
+    let names = (try? FileManager.default.contentsOfDirectory(atPath: directory.path)) ?? []
+    return names
+      .filter { $0.hasPrefix(prefix) && $0.hasSuffix(".log") }
+      .sorted()
+      .last
+      .map { directory.appendingPathComponent($0) }
+  }
+
+  private func readNewLines() {
+    // A new day starts a new file; pick it up without the user reopening the window.
+    let directory = fileURL?.deletingLastPathComponent()
+    if let directory = directory, let latest = latestLog(in: directory, prefix: source.prefix), latest != fileURL {
+      fileURL = latest
+      offset = 0
+      partialLine = ""
+    }
+    guard let fileURL = fileURL, let handle = try? FileHandle(forReadingFrom: fileURL) else { return }
+    defer { try? handle.close() }
+
+    let size = handle.seekToEndOfFile()
+    if size < offset {
+      offset = 0
+    }
+    guard size > offset else { return }
+    handle.seek(toFileOffset: offset)
+    let data = handle.readDataToEndOfFile()
+    offset += UInt64(data.count)
+
+    var chunk = partialLine + String(decoding: data, as: UTF8.self)
+    if let lastNewline = chunk.lastIndex(of: "\n") {
+      partialLine = String(chunk[chunk.index(after: lastNewline)...])
+      chunk = String(chunk[..<lastNewline])
+    } else {
+      partialLine = chunk
+      return
+    }
+    lines.append(contentsOf: chunk.split(separator: "\n", omittingEmptySubsequences: true).map(String.init))
+    if lines.count > LogViewerWindowController.maxLines {
+      lines.removeFirst(lines.count - LogViewerWindowController.maxLines)
+    }
+    render()
+  }
+
+  private func render() {
+    let minimum = levelPopup.indexOfSelectedItem
+    let visible = lines.filter { LogViewerWindowController.levelIndex(of: $0) >= minimum }
+    textView.string = visible.joined(separator: "\n")
+    if followCheckbox.state == .on {
+      textView.scrollToEndOfDocument(nil)
+    }
+  }
+
+  /// Understands both slog formats the backend can emit (`level=WARN` and `"level":"WARN"`).
+  private static func levelIndex(of line: String) -> Int {
+    for (index, level) in levels.enumerated().reversed()
+      where line.contains("level=\(level)") || line.contains("\"level\":\"\(level)\"") {
+      return index
+    }
+    return 1
+  }
+}
@@ -0,0 +1,120 @@
+import Foundation
+import Network
+
+/// Optional Prometheus endpoint on `127.0.0.1` (Help > Serve Metrics) so a local Grafana
+/// can scrape engine restarts, uptime and health latency. Health is probed on each scrape.
+final class MetricsServer {
+  struct Snapshot {
+    let version: String
+    let backendRunning: Bool
+    let backendStartedAt: Date?
+    let backendRestarts: Int
+    let healthURL: URL
+  }
+
+  static let defaultPort: UInt16 = 9464
+
+  private let queue = DispatchQueue(label: "com.investlog.metrics")
+  private var listener: NWListener?
+
+  var snapshotProvider: (() -> Snapshot)?
+
+  var isRunning: Bool {
+    return listener != nil
+  }
+
+  func start(port: UInt16) {
+    guard listener == nil, let endpointPort = NWEndpoint.Port(rawValue: port) else { return }
+    let parameters = NWParameters.tcp
+    parameters.requiredLocalEndpoint = .hostPort(host: "127.0.0.1", port: endpointPort)
+    parameters.allowLocalEndpointReuse = true
+    do {
+      let listener = try NWListener(using: parameters)
+      listener.newConnectionHandler = { [weak self] connection in
+        self?.serve(connection)
+      }
+      listener.stateUpdateHandler = { state in
+        switch state {
+        case .ready:
+          ShellLog.info("metrics server listening", ["addr": "127.0.0.1:\(port)"])
+        case .failed(let error):
+          ShellLog.error("metrics server failed", ["addr": "127.0.0.1:\(port)", "err": error.localizedDescription])
+        default:
+          break
+        }
+      }
+      listener.start(queue: queue)
+      self.listener = listener
+    } catch {
+      ShellLog.error("metrics server failed", ["addr": "127.0.0.1:\(port)", "err": error.localizedDescription])
+    }
+  }
+
+  func stop() {
+    listener?.cancel()
+    listener = nil
+    ShellLog.info("metrics server stopped")
+  }
+
+  private func serve(_ connection: NWConnection) {
+    connection.start(queue: queue)
+    connection.receive(minimumIncompleteLength: 1, maximumLength: 8192) { [weak self] data, _, _, _ in
+      let requestLine = data.flatMap { String(data: $0, encoding: .utf8) }?
+        .components(separatedBy: "\r\n").first ?? ""
+      let parts = requestLine.split(separator: " ")
+      guard parts.count >= 2, parts[0] == "GET", parts[1] == "/metrics" || parts[1].hasPrefix("/metrics?") else {
+        self?.respond(connection, status: "404 Not Found", body: "not found\n")
+        return
+      }
+      guard let snapshot = DispatchQueue.main.sync(execute: { self?.snapshotProvider?() }) else {
+        self?.respond(connection, status: "503 Service Unavailable", body: "shell not ready\n")
+        return
+      }
+      MetricsServer.probe(snapshot.healthURL) { latency in
+        self?.respond(connection, status: "200 OK", body: MetricsServer.render(snapshot, healthLatency: latency))
+      }
+    }
+  }
+
+  private func respond(_ connection: NWConnection, status: String, body: String) {
+    let bodyData = Data(body.utf8)
+    let head = "HTTP/1.1 \(status)\r\n"
+      + "Content-Type: text/plain; version=0.0.4; charset=utf-8\r\n"
+      + "Content-Length: \(bodyData.count)\r\n"
+      + "Connection: close\r\n\r\n"
+    connection.send(content: Data(head.utf8) + bodyData, completion: .contentProcessed { _ in
+      connection.cancel()
+    })
+  }
+
+  /// Seconds for one `/api/health` round trip, or nil if the engine did not answer.
+  private static func probe(_ url: URL, completion: @escaping (TimeInterval?) -> Void) {
+    var request = URLRequest(url: url)
+    request.timeoutInterval = 2.0
+    let started = Date()
+    BackendTLS.session.dataTask(with: request) { _, response, _ in
+      let ok = (response as? HTTPURLResponse)?.statusCode == 200
+      completion(ok ? Date().timeIntervalSince(started) : nil)
+    }.resume()
+  }
+
+  private static func render(_ snapshot: Snapshot, healthLatency: TimeInterval?) -> String {
+    var lines: [String] = []
+    func metric(_ name: String, _ type: String, _ help: String, _ value: String, labels: String = "") {
+      lines.append("# HELP \(name) \(help)")
+      lines.append("# TYPE \(name) \(type)")
+      lines.append("\(name)\(labels) \(value)")
+    }
+    let version = snapshot.version.replacingOccurrences(of: "\"", with: "")
+    metric("investlog_shell_info", "gauge", "Shell build information.", "1", labels: "{version=\"\(version)\"}")
+    metric("investlog_backend_up", "gauge", "Whether the engine answered its health check.", healthLatency == nil ? "0" : "1")
+    metric("investlog_backend_running", "gauge", "Whether the engine process is running.", snapshot.backendRunning ? "1" : "0")
+    let uptime = snapshot.backendRunning ? snapshot.backendStartedAt.map { Date().timeIntervalSince($0) } ?? 0 : 0
+    metric("investlog_backend_uptime_seconds", "gauge", "Seconds since the engine process was started.", String(format: "%.0f", uptime))
+    metric("investlog_backend_restarts_total", "counter", "Engine restarts since the shell launched.", "\(snapshot.backendRestarts)")
+    if let latency = healthLatency {
+      metric("investlog_backend_health_latency_seconds", "gauge", "Latency of the engine health check.", String(format: "%.4f", latency))
+    }
+    return lines.joined(separator: "\n") + "\n"
+  }
+}
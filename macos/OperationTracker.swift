@@ -0,0 +1,33 @@
+import Foundation
+
+/// Long-running shell work (copying or switching databases, …) that quitting would interrupt.
+final class OperationTracker {
+  private var active: [UUID: String] = [:]
+  private var startedAt: [UUID: Date] = [:]
+
+  var onChange: (() -> Void)?
+
+  var isBusy: Bool {
+    return !active.isEmpty
+  }
+
+  var labels: [String] {
+    return active.values.sorted()
+  }
+
+  func begin(_ label: String) -> UUID {
+    let id = UUID()
+    active[id] = label
+    startedAt[id] = Date()
+    ShellLog.info("operation started", ["operation": label])
+    onChange?()
+    return id
+  }
+
+  func end(_ id: UUID) {
+    guard let label = active.removeValue(forKey: id) else { return }
+    let elapsed = startedAt.removeValue(forKey: id).map { Int(Date().timeIntervalSince($0) * 1000) } ?? 0
+    ShellLog.info("operation finished", ["operation": label, "elapsed_ms": elapsed])
+    onChange?()
+  }
+}
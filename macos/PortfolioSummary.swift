@@ -0,0 +1,102 @@
+import Foundation
+
+/// Compact view of `/api/holdings-by-symbol` used by the menu bar.
+struct PortfolioSummary {
+  struct CurrencyTotal {
+    let currency: String
+    let marketValue: Double
+    let pnl: Double
+  }
+
+  struct Position {
+    let currency: String
+    let displayName: String
+    let marketValue: Double
+    let pnlPercent: Double?
+  }
+
+  let totals: [CurrencyTotal]
+  let topPositions: [Position]
+
+  /// Set by the privacy shield; summary views then show placeholders instead of figures.
+  static var hidesAmounts = false
+
+  private struct SymbolPayload: Decodable {
+    let symbol: String
+    let displayName: String?
+    let marketValue: Double
+    let pnlPercent: Double?
+
+    enum CodingKeys: String, CodingKey {
+      case symbol
+      case displayName = "display_name"
+      case marketValue = "market_value"
+      case pnlPercent = "pnl_percent"
+    }
+  }
+
+  private struct CurrencyPayload: Decodable {
+    let totalMarketValue: Double
+    let totalPnL: Double
+    let symbols: [SymbolPayload]
+
+    enum CodingKeys: String, CodingKey {
+      case totalMarketValue = "total_market_value"
+      case totalPnL = "total_pnl"
+      case symbols
+    }
+  }
+
+  static func fetch(baseURL: URL, limit: Int = 3, completion: @escaping (PortfolioSummary?) -> Void) {
+    var request = URLRequest(url: baseURL.appendingPathComponent("api/holdings-by-symbol"))
+    request.timeoutInterval = 5.0
+
+    BackendTLS.session.dataTask(with: request) { data, response, _ in
+      var summary: PortfolioSummary?
+      if let http = response as? HTTPURLResponse, http.statusCode == 200, let data = data,
+         let payload = try? JSONDecoder().decode([String: CurrencyPayload].self, from: data) {
+        summary = PortfolioSummary(payload: payload, limit: limit)
+      }
+      DispatchQueue.main.async { completion(summary) }
+    }.resume()
+  }
+
+  private init(payload: [String: CurrencyPayload], limit: Int) {
+    totals = payload
+      .map { CurrencyTotal(currency: $0.key, marketValue: $0.value.totalMarketValue, pnl: $0.value.totalPnL) }
+      .sorted { $0.currency < $1.currency }
+    topPositions = payload
+      .flatMap { entry in
+        entry.value.symbols.map {
+          Position(
+            currency: entry.key,
+            displayName: $0.displayName ?? $0.symbol,
+            marketValue: $0.marketValue,
+            pnlPercent: $0.pnlPercent
+          )
+        }
+      }
+      .sorted { $0.marketValue > $1.marketValue }
+      .prefix(limit)
+      .map { $0 }
+  }
+
+  static func formatAmount(_ value: Double, currency: String) -> String {
+    if hidesAmounts {
+      return currency.isEmpty ? "••••" : "\(currency) ••••"
+    }
+    let formatter = NumberFormatter()
+    formatter.numberStyle = .decimal
+    formatter.maximumFractionDigits = 2
+    formatter.minimumFractionDigits = 2
+    let number = formatter.string(from: NSNumber(value: value)) ?? String(format: "%.2f", value)
+    return currency.isEmpty ? number : "\(currency) \(number)"
+  }
+
+  static func formatPercent(_ value: Double) -> String {
+    if hidesAmounts {
+      return "••%"
+    }
+    return String(format: "%+.2f%%", value)
+  }
+}
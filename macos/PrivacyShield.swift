@@ -0,0 +1,90 @@
+import AppKit
+
+/// Opt-in shield (View > Hide Contents When Inactive): blurs every app window while Invest
+/// Log is in the background and masks amounts in the menu bar and mini ticker then, or while
+/// a screen-sharing app is running. Driven by AppKit, so it works even if the page is busy.
+final class PrivacyShield {
+  static var isEnabled: Bool {
+    get { return UserDefaults.standard.bool(forKey: "PrivacyShield") }
+    set { UserDefaults.standard.set(newValue, forKey: "PrivacyShield") }
+  }
+
+  /// Apps whose presence suggests the screen may be shared or recorded.
+  private static let screenSharingBundleIDs: Set<String> = [
+    "us.zoom.xos",
+    "com.microsoft.teams",
+    "com.microsoft.teams2",
+    "com.cisco.webexmeetingsapp",
+    "com.webex.meetingmanager",
+    "com.apple.ScreenSharing",
+    "com.apple.screensharing.agent",
+    "com.teamviewer.TeamViewer",
+    "com.anydesk.anydeskmacos",
+    "com.obsproject.obs-studio",
+    "com.loom.desktop",
+  ]
+
+  private var overlays: [ObjectIdentifier: NSView] = [:]
+  private(set) var masksAmounts = false
+
+  /// Called when `masksAmounts` flips, so summary views can redraw.
+  var onMaskChange: (() -> Void)?
+
+  static var screenShareLikely: Bool {
+    return NSWorkspace.shared.runningApplications.contains {
+      $0.bundleIdentifier.map(screenSharingBundleIDs.contains) == true
+    }
+  }
+
+  /// `windows` are covered when the app is inactive and uncovered otherwise.
+  func update(windows: [NSWindow], appActive: Bool) {
+    let enabled = PrivacyShield.isEnabled
+    if enabled && !appActive {
+      windows.forEach(cover)
+    } else {
+      overlays.values.forEach { $0.removeFromSuperview() }
+      overlays = [:]
+    }
+    let mask = enabled && (!appActive || PrivacyShield.screenShareLikely)
+    if mask != masksAmounts {
+      masksAmounts = mask
+      onMaskChange?()
+    }
+  }
+
+  private func cover(_ window: NSWindow) {
+    guard overlays[ObjectIdentifier(window)] == nil, let content = window.contentView else { return }
+    let overlay = PrivacyOverlayView(frame: content.bounds)
+    overlay.autoresizingMask = [.width, .height]
+    content.addSubview(overlay)
+    overlays[ObjectIdentifier(window)] = overlay
+  }
+}
+
+private final class PrivacyOverlayView: NSVisualEffectView {
+  override init(frame frameRect: NSRect) {
+    super.init(frame: frameRect)
+    material = .fullScreenUI
+    blendingMode = .withinWindow
+    state = .active
+
+    let label = NSTextField(labelWithString: "Contents hidden")
+    label.font = .systemFont(ofSize: 15, weight: .medium)
+    label.textColor = .secondaryLabelColor
+    label.translatesAutoresizingMaskIntoConstraints = false
+    addSubview(label)
+    NSLayoutConstraint.activate([
+      label.centerXAnchor.constraint(equalTo: centerXAnchor),
+      label.centerYAnchor.constraint(equalTo: centerYAnchor),
+    ])
+  }
+
+  required init?(coder: NSCoder) {
+    fatalError("init(coder:) has not been implemented")
+  }
+
+  // Purely visual: the click that brings the app forward also removes the shield.
+  override func hitTest(_ point: NSPoint) -> NSView? {
+    return nil
+  }
+}
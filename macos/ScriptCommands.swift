@@ -0,0 +1,131 @@
+import AppKit
+
+/// AppleScript commands declared in InvestLog.sdef. Both talk to the running backend like
+/// the SPA does, so they are only available once it is up and the app is unlocked. Shortcuts
+/// reaches them through its "Run AppleScript" action.
+class InvestLogScriptCommand: NSScriptCommand {
+  /// Base URL of the backend, or nil after reporting why scripts can't use it right now.
+  func backendURLForScripting() -> URL? {
+    guard let app = NSApp.delegate as? AppDelegate else {
+      fail("Invest Log is not ready.")
+      return nil
+    }
+    if app.isLockedForScripting {
+      fail("Invest Log is locked. Unlock it and try again.")
+      return nil
+    }
+    guard let url = app.scriptingBackendURL else {
+      fail("The Invest Log backend is not running.")
+      return nil
+    }
+    return url
+  }
+
+  func fail(_ message: String) {
+    scriptErrorNumber = Int(errAEEventFailed)
+    scriptErrorString = message
+  }
+
+  /// Resumes a suspended command from the URLSession callback queue.
+  func finish(result: Any?, error: String?) {
+    DispatchQueue.main.async {
+      if let error = error {
+        self.fail(error)
+      }
+      self.resumeExecution(withResult: result)
+    }
+  }
+
+  /// The backend reports failures as `{"error": "..."}`.
+  static func backendError(_ data: Data?, status: Int) -> String {
+    if let data = data,
+       let body = try? JSONSerialization.jsonObject(with: data) as? [String: Any],
+       let message = body["error"] as? String {
+      return message
+    }
+    return "The backend returned HTTP \(status)."
+  }
+}
+
+/// `total value [in "USD"]`: market value of all holdings in one currency, as shown in the menu bar.
+@objc(InvestLogTotalValueCommand)
+class InvestLogTotalValueCommand: InvestLogScriptCommand {
+  override func performDefaultImplementation() -> Any? {
+    let currency = ((evaluatedArguments?["currency"] as? String) ?? "CNY").uppercased()
+    guard ["CNY", "USD", "HKD"].contains(currency) else {
+      fail("Unsupported currency: \(currency). Use CNY, USD or HKD.")
+      return nil
+    }
+    guard let baseURL = backendURLForScripting() else { return nil }
+
+    suspendExecution()
+    PortfolioSummary.fetch(baseURL: baseURL) { summary in
+      guard let summary = summary else {
+        self.finish(result: nil, error: "Could not read holdings from the backend.")
+        return
+      }
+      let total = summary.totals.first { $0.currency == currency }?.marketValue ?? 0
+      self.finish(result: total, error: nil)
+    }
+    return nil
+  }
+}
+
+/// `add transaction symbol "AAPL" kind "BUY" quantity 10 price 190 account "broker"`; returns the new ID.
+@objc(InvestLogAddTransactionCommand)
+class InvestLogAddTransactionCommand: InvestLogScriptCommand {
+  override func performDefaultImplementation() -> Any? {
+    let arguments = evaluatedArguments ?? [:]
+    guard let symbol = arguments["symbol"] as? String,
+          let kind = arguments["kind"] as? String,
+          let quantity = (arguments["quantity"] as? NSNumber)?.doubleValue,
+          let account = arguments["account"] as? String else {
+      fail("symbol, kind, quantity and account are required.")
+      return nil
+    }
+    guard let baseURL = backendURLForScripting() else { return nil }
+
+    var payload: [String: Any] = [
+      "symbol": symbol.uppercased(),
+      "transaction_type": kind.uppercased(),
+      "quantity": quantity,
+      "price": (arguments["price"] as? NSNumber)?.doubleValue ?? 0,
+      "account_id": account,
+    ]
+    if let currency = arguments["currency"] as? String {
+      payload["currency"] = currency.uppercased()
+    }
+    if let date = arguments["date"] as? String {
+      payload["transaction_date"] = date
+    }
+    if let notes = arguments["notes"] as? String {
+      payload["notes"] = notes
+    }
+
+    var request = URLRequest(url: baseURL.appendingPathComponent("api/transactions"))
+    request.httpMethod = "POST"
+    request.setValue("application/json", forHTTPHeaderField: "Content-Type")
+    request.httpBody = try? JSONSerialization.data(withJSONObject: payload)
+    request.timeoutInterval = 10.0
+
+    suspendExecution()
+    BackendTLS.session.dataTask(with: request) { data, response, error in
+      if let error = error {
+        self.finish(result: nil, error: error.localizedDescription)
+        return
+      }
+      let status = (response as? HTTPURLResponse)?.statusCode ?? 0
+      guard status == 200, let data = data,
+            let body = try? JSONSerialization.jsonObject(with: data) as? [String: Any],
+            let id = body["id"] as? NSNumber else {
+        self.finish(result: nil, error: InvestLogScriptCommand.backendError(data, status: status))
+        return
+      }
+      DispatchQueue.main.async {
+        (NSApp.delegate as? AppDelegate)?.scriptingDidChangeTransactions()
+      }
+      self.finish(result: id.intValue, error: nil)
+    }.resume()
+    return nil
+  }
+}
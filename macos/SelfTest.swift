@@ -0,0 +1,91 @@
+import CryptoKit
+import Darwin
+import Foundation
+
+/// Shell-side checks plus the backend's `/api/self-test`, merged into one report.
+enum SelfTest {
+  struct Check: Decodable {
+    let name: String
+    let status: String
+    let detail: String?
+  }
+
+  private struct BackendReport: Decodable {
+    let checks: [Check]
+  }
+
+  /// Runs everything off the main thread and calls back on it.
+  static func run(resourcesURL: URL, host: String, port: Int, completion: @escaping ([Check]) -> Void) {
+    DispatchQueue.global(qos: .userInitiated).async {
+      var checks = [checkBackendBinary(in: resourcesURL)]
+      let backend = fetchBackendChecks(host: host, port: port)
+      checks.append(checkPort(host: host, port: port, backendAnswered: backend != nil))
+      checks += backend ?? [Check(name: "backend_self_test", status: "fail", detail: "backend did not respond")]
+      DispatchQueue.main.async { completion(checks) }
+    }
+  }
+
+  static func format(_ checks: [Check]) -> String {
+    return checks.map { check in
+      let mark = check.status == "pass" ? "✓" : (check.status == "skip" ? "–" : "✗")
+      return "\(mark) \(check.name)" + (check.detail.map { ": \($0)" } ?? "")
+    }.joined(separator: "\n")
+  }
+
+  /// The build records the binary's SHA-256 next to it; a mismatch means a damaged or swapped file.
+  private static func checkBackendBinary(in resourcesURL: URL) -> Check {
+    let binaryURL = BackendBinary.url(in: resourcesURL)
+    guard FileManager.default.isExecutableFile(atPath: binaryURL.path) else {
+      return Check(name: "backend_binary", status: "fail", detail: "missing at \(binaryURL.path)")
+    }
+    guard let expected = try? String(contentsOf: binaryURL.appendingPathExtension("sha256"), encoding: .utf8)
+      .trimmingCharacters(in: .whitespacesAndNewlines), !expected.isEmpty else {
+      return Check(name: "backend_binary", status: "skip", detail: "no recorded checksum")
+    }
+    guard let data = try? Data(contentsOf: binaryURL, options: .mappedIfSafe) else {
+      return Check(name: "backend_binary", status: "fail", detail: "unreadable")
+    }
+    let actual = SHA256.hash(data: data).map { String(format: "%02x", $0) }.joined()
+    return actual == expected
+      ? Check(name: "backend_binary", status: "pass", detail: "checksum matches")
+      : Check(name: "backend_binary", status: "fail", detail: "checksum mismatch")
+  }
+
+  /// With our backend answering the port is fine; otherwise it must at least be free to bind.
+  private static func checkPort(host: String, port: Int, backendAnswered: Bool) -> Check {
+    if backendAnswered {
+      return Check(name: "port", status: "pass", detail: "\(host):\(port) served by the backend")
+    }
+    let fd = socket(AF_INET, SOCK_STREAM, 0)
+    guard fd >= 0 else {
+      return Check(name: "port", status: "fail", detail: "socket: \(String(cString: strerror(errno)))")
+    }
+    defer { close(fd) }
+    var address = sockaddr_in()
+    address.sin_family = sa_family_t(AF_INET)
+    address.sin_port = in_port_t(UInt16(port).bigEndian)
+    address.sin_addr.s_addr = inet_addr(host)
+    let result = withUnsafePointer(to: &address) {
+      $0.withMemoryRebound(to: sockaddr.self, capacity: 1) { bind(fd, $0, socklen_t(MemoryLayout<sockaddr_in>.size)) }
+    }
+    return result == 0
+      ? Check(name: "port", status: "pass", detail: "\(host):\(port) is free")
+      : Check(name: "port", status: "fail", detail: "\(host):\(port) is in use by another process")
+  }
+
+  private static func fetchBackendChecks(host: String, port: Int) -> [Check]? {
+    guard let url = URL(string: "\(BackendTLS.scheme)://\(host):\(port)/api/self-test") else { return nil }
+    var request = URLRequest(url: url)
+    request.timeoutInterval = 10.0
+    let semaphore = DispatchSemaphore(value: 0)
+    var checks: [Check]?
+    BackendTLS.session.dataTask(with: request) { data, response, _ in
+      if let http = response as? HTTPURLResponse, http.statusCode == 200, let data = data {
+        checks = (try? JSONDecoder().decode(BackendReport.self, from: data))?.checks
+      }
+      semaphore.signal()
+    }.resume()
+    semaphore.wait()
+    return checks
+  }
+}
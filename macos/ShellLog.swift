@@ -0,0 +1,187 @@
+import CryptoKit
+import Foundation
+
+/// Daily-rotated text log for the shell, kept as `shell-YYYYMMDD.log` in the app's
+/// Application Support folder so it exists before the backend (and its data dir) is up.
+/// Lines mirror the backend's slog text format: `time=… level=INFO msg="…" key=value`.
+final class ShellLog {
+  enum Level: String {
+    case debug = "DEBUG"
+    case info = "INFO"
+    case warn = "WARN"
+    case error = "ERROR"
+
+    var rank: Int {
+      switch self {
+      case .debug: return 0
+      case .info: return 1
+      case .warn: return 2
+      case .error: return 3
+      }
+    }
+  }
+
+  static let shared = ShellLog()
+
+  /// Lines below this are dropped; changed at runtime from the Log Level menus.
+  static var minimumLevel = Level.info
+
+  static let directory: URL = FileManager.default
+    .urls(for: .applicationSupportDirectory, in: .userDomainMask)[0]
+    .appendingPathComponent("InvestLog", isDirectory: true)
+    .appendingPathComponent("logs", isDirectory: true)
+
+  private static let prefix = "shell-"
+  private static let retentionDays = 7
+
+  private let queue = DispatchQueue(label: "com.investlog.shell-log")
+  private let timestampFormatter = ISO8601DateFormatter()
+  private let dateFormatter: DateFormatter
+  private var currentDate = ""
+  private var handle: FileHandle?
+
+  private init() {
+    timestampFormatter.formatOptions = [.withInternetDateTime, .withFractionalSeconds]
+    dateFormatter = DateFormatter()
+    dateFormatter.locale = Locale(identifier: "en_US_POSIX")
+    dateFormatter.dateFormat = "yyyyMMdd"
+  }
+
+  static func debug(_ message: String, _ attributes: KeyValuePairs<String, Any> = [:]) {
+    shared.write(.debug, message, attributes)
+  }
+
+  static func info(_ message: String, _ attributes: KeyValuePairs<String, Any> = [:]) {
+    shared.write(.info, message, attributes)
+  }
+
+  static func warn(_ message: String, _ attributes: KeyValuePairs<String, Any> = [:]) {
+    shared.write(.warn, message, attributes)
+  }
+
+  static func error(_ message: String, _ attributes: KeyValuePairs<String, Any> = [:]) {
+    shared.write(.error, message, attributes)
+  }
+
+  private func write(_ level: Level, _ message: String, _ attributes: KeyValuePairs<String, Any>) {
+    guard level.rank >= ShellLog.minimumLevel.rank else { return }
+    let now = Date()
+    var line = "time=\(timestampFormatter.string(from: now)) level=\(level.rawValue) msg=\(ShellLog.quote(message)) service=shell"
+    for (key, value) in attributes {
+      line += " \(key)=\(ShellLog.quote(ShellLog.redactAttribute(key, String(describing: value))))"
+    }
+    line = ShellLog.redact(line) + "\n"
+
+    queue.async {
+      FileHandle.standardError.write(Data(line.utf8))
+      self.rotateIfNeeded(now)
+      self.handle?.write(Data(line.utf8))
+    }
+  }
+
+  private static let redacted = "[REDACTED]"
+
+  /// The backend's logging.Redact patterns, so shell logs and diagnostics are as safe to share.
+  private static let secretPatterns: [NSRegularExpression] = [
+    #"(?i)(bearer\s+)[A-Za-z0-9._~+/=-]{8,}"#,
+    #"\b(sk-|AIza)[A-Za-z0-9_-]{8,}"#,
+    #"(?i)("?(?:api[_-]?key|access[_-]?token|token|secret|password)"?\s*[:=]\s*"?)[^"\s,&}]+"#,
+    #"(/api/accounts/)[^/?\s"]+"#,
+  ].map { try! NSRegularExpression(pattern: $0) }
+
+  /// `account=…` pairs already in a line (e.g. backend output), hashed like attributes.
+  private static let accountPairPattern = try! NSRegularExpression(
+    pattern: #"(?i)\b(account(?:_id|_name)?=)("[^"]*"|[^\s"]+)"#
+  )
+
+  /// Attribute keys whose values are never written, and keys naming the user's accounts.
+  private static let secretKeys: Set<String> = ["api_key", "apikey", "token", "secret", "password", "authorization"]
+  private static let accountKeys: Set<String> = ["account", "account_id", "account_name"]
+
+  /// Masks the home directory, API keys, bearer tokens, secret-looking key/value pairs and
+  /// account IDs, as the backend's logging.Redact does.
+  static func redact(_ text: String) -> String {
+    var result = text
+    let home = NSHomeDirectory()
+    if home.count > 1 {
+      result = result.replacingOccurrences(of: home, with: "~")
+    }
+    for pattern in secretPatterns {
+      let range = NSRange(result.startIndex..., in: result)
+      result = pattern.stringByReplacingMatches(in: result, range: range, withTemplate: "$1\(redacted)")
+    }
+    return hashAccountPairs(result)
+  }
+
+  private static func redactAttribute(_ key: String, _ value: String) -> String {
+    let key = key.lowercased()
+    if secretKeys.contains(key) {
+      return redacted
+    }
+    if accountKeys.contains(key) {
+      return hashIdentifier(value)
+    }
+    return value
+  }
+
+  private static func hashAccountPairs(_ text: String) -> String {
+    var result = text
+    let matches = accountPairPattern.matches(in: text, range: NSRange(text.startIndex..., in: text))
+    for match in matches.reversed() {
+      guard let valueRange = Range(match.range(at: 2), in: result) else { continue }
+      var value = String(result[valueRange])
+      if value.hasPrefix("\""), value.hasSuffix("\""), value.count >= 2 {
+        value = String(value.dropFirst().dropLast())
+      }
+      guard !value.isEmpty, !value.hasPrefix("acct-") else { continue }
+      result.replaceSubrange(valueRange, with: hashIdentifier(value))
+    }
+    return result
+  }
+
+  /// Same short SHA-256 prefix as the backend, so one account correlates across both logs.
+  private static func hashIdentifier(_ value: String) -> String {
+    guard !value.isEmpty else { return value }
+    let digest = SHA256.hash(data: Data(value.utf8))
+    return "acct-" + digest.prefix(4).map { String(format: "%02x", $0) }.joined()
+  }
+
+  private static func quote(_ value: String) -> String {
+    let needsQuotes = value.isEmpty || value.contains { $0 == " " || $0 == "=" || $0 == "\"" || $0.isNewline }
+    guard needsQuotes else { return value }
+    let escaped = value
+      .replacingOccurrences(of: "\\", with: "\\\\")
+      .replacingOccurrences(of: "\"", with: "\\\"")
+      .replacingOccurrences(of: "\n", with: "\\n")
+    return "\"\(escaped)\""
+  }
+
+  private func rotateIfNeeded(_ now: Date) {
+    let date = dateFormatter.string(from: now)
+    guard date != currentDate || handle == nil else { return }
+    try? handle?.close()
+    handle = nil
+    currentDate = date
+
+    let fileManager = FileManager.default
+    try? fileManager.createDirectory(at: ShellLog.directory, withIntermediateDirectories: true)
+    let url = ShellLog.directory.appendingPathComponent("\(ShellLog.prefix)\(date).log")
+    if !fileManager.fileExists(atPath: url.path) {
+      fileManager.createFile(atPath: url.path, contents: nil)
+    }
+    handle = try? FileHandle(forWritingTo: url)
+    handle?.seekToEndOfFile()
+    cleanup(now)
+  }
+
+  private func cleanup(_ now: Date) {
+    guard let cutoff = Calendar.current.date(byAdding: .day, value: -ShellLog.retentionDays, to: now),
+          let names = try? FileManager.default.contentsOfDirectory(atPath: ShellLog.directory.path) else { return }
+    for name in names where name.hasPrefix(ShellLog.prefix) && name.hasSuffix(".log") {
+      let datePart = String(name.dropFirst(ShellLog.prefix.count).dropLast(".log".count))
+      if let date = dateFormatter.date(from: datePart), date < cutoff {
+        try? FileManager.default.removeItem(at: ShellLog.directory.appendingPathComponent(name))
+      }
+    }
+  }
+}
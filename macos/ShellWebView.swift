@@ -0,0 +1,59 @@
+import AppKit
+import WebKit
+
+/// Web view whose right-click menu is built by the shell instead of WebKit.
+final class ShellWebView: WKWebView {
+  /// What the page last reported under the pointer (`contextmenu` → `shellContext` message).
+  struct MenuContext {
+    var hasSelection = false
+    var symbol: String?
+    var currency: String?
+    var account: String?
+  }
+
+  var menuContext = MenuContext()
+  var menuBuilder: ((ShellWebView, NSMenu) -> Void)?
+
+  override func willOpenMenu(_ menu: NSMenu, with event: NSEvent) {
+    guard let menuBuilder = menuBuilder else {
+      super.willOpenMenu(menu, with: event)
+      return
+    }
+    menu.removeAllItems()
+    menuBuilder(self, menu)
+    // The next right-click reports afresh; stale rows must not leak into it.
+    menuContext = MenuContext()
+  }
+
+  func updateMenuContext(from body: Any) {
+    guard let payload = body as? [String: Any] else { return }
+    menuContext = MenuContext(
+      hasSelection: payload["selection"] as? Bool ?? false,
+      symbol: nonEmpty(payload["symbol"]),
+      currency: nonEmpty(payload["currency"]),
+      account: nonEmpty(payload["account"])
+    )
+  }
+
+  private func nonEmpty(_ value: Any?) -> String? {
+    guard let text = value as? String, !text.isEmpty else { return nil }
+    return text
+  }
+}
+
+extension WKWebView {
+  /// The one way the shell runs script in a page: a fixed function body with values passed
+  /// as arguments, so routes, settings and error text are never spliced into source.
+  func callShellScript(_ body: String, _ arguments: [String: Any] = [:]) {
+    callAsyncJavaScript(body, arguments: arguments, in: nil, in: .page, completionHandler: nil)
+  }
+
+  /// Fires `investlog:shell` on the page's `window` with `{ type, detail }`; the SPA
+  /// subscribes with `onShellEvent(type, handler)`.
+  func publishShellEvent(_ type: String, _ detail: [String: Any] = [:]) {
+    callShellScript(
+      "window.dispatchEvent(new CustomEvent('investlog:shell', { detail: { type, detail } }))",
+      ["type": type, "detail": detail]
+    )
+  }
+}
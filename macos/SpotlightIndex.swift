@@ -0,0 +1,107 @@
+import CoreSpotlight
+import Foundation
+import UniformTypeIdentifiers
+
+/// Puts transactions into Spotlight (symbol, type, date and note; no amounts) so a search
+/// such as "AAPL dividend" opens the transactions page filtered to that day, with the
+/// transaction itself scrolled into view and highlighted. Each item's identifier is its
+/// `investlog://` link, which DeepLink turns back into a route.
+/// Nothing is indexed while the app lock is on, since Spotlight would show data without it.
+enum SpotlightIndex {
+  static let domain = "com.investlog.transactions"
+
+  /// The most recent transactions are enough to find things by name; older ones drop out.
+  private static let limit = 5000
+
+  private struct TransactionPayload: Decodable {
+    let id: Int64
+    let transactionDate: String
+    let symbol: String
+    let name: String?
+    let transactionType: String
+    let accountID: String
+    let notes: String?
+
+    enum CodingKeys: String, CodingKey {
+      case id
+      case transactionDate = "transaction_date"
+      case symbol
+      case name
+      case transactionType = "transaction_type"
+      case accountID = "account_id"
+      case notes
+    }
+  }
+
+  static func refresh(baseURL: URL) {
+    guard CSSearchableIndex.isIndexingAvailable() else { return }
+    guard !AppLock.isEnabled else {
+      clear()
+      return
+    }
+    var components = URLComponents(url: baseURL.appendingPathComponent("api/transactions"), resolvingAgainstBaseURL: false)!
+    components.queryItems = [URLQueryItem(name: "limit", value: String(limit))]
+    var request = URLRequest(url: components.url!)
+    request.timeoutInterval = 30.0
+
+    BackendTLS.session.dataTask(with: request) { data, response, _ in
+      guard (response as? HTTPURLResponse)?.statusCode == 200, let data = data,
+            let transactions = try? JSONDecoder().decode([TransactionPayload].self, from: data) else {
+        ShellLog.warn("spotlight refresh skipped: transactions unavailable")
+        return
+      }
+      let items = transactions.compactMap(searchableItem)
+      // Replacing the whole domain also removes transactions deleted since the last run.
+      CSSearchableIndex.default().deleteSearchableItems(withDomainIdentifiers: [domain]) { _ in
+        CSSearchableIndex.default().indexSearchableItems(items) { error in
+          if let error = error {
+            ShellLog.warn("spotlight indexing failed", ["error": error.localizedDescription])
+          } else {
+            ShellLog.debug("spotlight index refreshed", ["items": items.count])
+          }
+        }
+      }
+    }.resume()
+  }
+
+  static func clear() {
+    CSSearchableIndex.default().deleteSearchableItems(withDomainIdentifiers: [domain], completionHandler: nil)
+  }
+
+  /// The link behind a Spotlight result the user opened, if it is one of ours.
+  static func link(for activity: NSUserActivity) -> URL? {
+    guard activity.activityType == CSSearchableItemActionType,
+          let identifier = activity.userInfo?[CSSearchableItemActivityIdentifier] as? String,
+          let url = URL(string: identifier), url.scheme == DeepLink.scheme else {
+      return nil
+    }
+    return url
+  }
+
+  private static func searchableItem(_ transaction: TransactionPayload) -> CSSearchableItem? {
+    var link = URLComponents()
+    link.scheme = DeepLink.scheme
+    link.host = "transactions"
+    link.queryItems = [
+      URLQueryItem(name: "symbol", value: transaction.symbol),
+      URLQueryItem(name: "start_date", value: transaction.transactionDate),
+      URLQueryItem(name: "end_date", value: transaction.transactionDate),
+      URLQueryItem(name: "id", value: String(transaction.id)),
+    ]
+    guard let identifier = link.url?.absoluteString else { return nil }
+
+    let kind = transaction.transactionType.replacingOccurrences(of: "_", with: " ").capitalized
+    let attributes = CSSearchableItemAttributeSet(contentType: .content)
+    attributes.title = "\(transaction.symbol) \(kind)"
+    attributes.contentDescription = [transaction.transactionDate, transaction.name, transaction.notes]
+      .compactMap { $0 }
+      .filter { !$0.isEmpty }
+      .joined(separator: " · ")
+    attributes.keywords = [transaction.symbol, kind, transaction.accountID, "Invest Log"]
+      + (transaction.name.map { [$0] } ?? [])
+
+    let item = CSSearchableItem(uniqueIdentifier: identifier, domainIdentifier: domain, attributeSet: attributes)
+    item.expirationDate = .distantFuture
+    return item
+  }
+}
@@ -0,0 +1,54 @@
+import Foundation
+
+/// Timestamps of the launch phases, relative to process start, for diagnosing slow starts.
+final class StartupTimeline {
+  enum Phase: String, CaseIterable {
+    case launched = "App launched"
+    case backendSpawned = "Backend spawned"
+    case windowReady = "Window created"
+    case backendHealthy = "First health check OK"
+    case firstPageLoaded = "First page loaded"
+  }
+
+  private let processStart: Date
+  private var marks: [Phase: Date] = [:]
+
+  init() {
+    // kinfo_proc carries the real exec time, so dyld and AppKit setup are counted too.
+    var info = kinfo_proc()
+    var size = MemoryLayout<kinfo_proc>.stride
+    var mib: [Int32] = [CTL_KERN, KERN_PROC, KERN_PROC_PID, getpid()]
+    if sysctl(&mib, u_int(mib.count), &info, &size, nil, 0) == 0 {
+      let start = info.kp_proc.p_starttime
+      processStart = Date(timeIntervalSince1970: TimeInterval(start.tv_sec) + TimeInterval(start.tv_usec) / 1_000_000)
+    } else {
+      processStart = Date()
+    }
+  }
+
+  /// Only the first occurrence counts; a restart starts the backend phases over via `resetBackendPhases()`.
+  func mark(_ phase: Phase) {
+    guard marks[phase] == nil else { return }
+    let now = Date()
+    marks[phase] = now
+    ShellLog.info("startup timing", ["phase": phase.rawValue, "since_launch_ms": milliseconds(since: processStart, to: now)])
+  }
+
+  func resetBackendPhases() {
+    marks[.backendSpawned] = nil
+    marks[.backendHealthy] = nil
+    marks[.firstPageLoaded] = nil
+  }
+
+  /// One line per phase, e.g. `Backend spawned: +412 ms`.
+  var summary: String {
+    return Phase.allCases.map { phase in
+      guard let date = marks[phase] else { return "\(phase.rawValue): —" }
+      return "\(phase.rawValue): +\(milliseconds(since: processStart, to: date)) ms"
+    }.joined(separator: "\n")
+  }
+
+  private func milliseconds(since start: Date, to end: Date) -> Int {
+    return Int(end.timeIntervalSince(start) * 1000)
+  }
+}
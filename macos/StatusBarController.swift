@@ -0,0 +1,166 @@
+import AppKit
+
+/// Menu bar item giving quick access to the window and the bundled backend.
+final class StatusBarController: NSObject, NSMenuDelegate {
+  private let statusItem: NSStatusItem
+  private let menu = NSMenu()
+  private var summaryItems: [NSMenuItem] = []
+  private let backendItem = NSMenuItem(title: "", action: nil, keyEquivalent: "")
+  private let toggleWindowItem = NSMenuItem(title: "Hide Invest Log", action: nil, keyEquivalent: "")
+  private let toggleTickerItem = NSMenuItem(title: "Show Mini Ticker", action: nil, keyEquivalent: "")
+  private let logLevelMenu = NSMenu(title: "Log Level")
+
+  var onToggleWindow: (() -> Void)?
+  var onRestartBackend: (() -> Void)?
+  var onMenuWillOpen: (() -> Void)?
+  var onToggleTicker: (() -> Void)?
+  var onSelectLogLevel: ((ShellLog.Level) -> Void)?
+
+  override init() {
+    statusItem = NSStatusBar.system.statusItem(withLength: NSStatusItem.variableLength)
+    super.init()
+
+    if let button = statusItem.button {
+      if let image = NSImage(named: "AppIcon") {
+        image.size = NSSize(width: 18, height: 18)
+        button.image = image
+      } else {
+        button.title = "IL"
+      }
+      button.toolTip = "Invest Log"
+    }
+
+    menu.autoenablesItems = false
+    menu.delegate = self
+
+    toggleWindowItem.action = #selector(toggleWindow(_:))
+    toggleWindowItem.target = self
+    menu.addItem(toggleWindowItem)
+    toggleTickerItem.action = #selector(toggleTicker(_:))
+    toggleTickerItem.target = self
+    menu.addItem(toggleTickerItem)
+    menu.addItem(NSMenuItem.separator())
+
+    backendItem.isEnabled = false
+    menu.addItem(backendItem)
+    let restartItem = menu.addItem(withTitle: "Restart Backend", action: #selector(restartBackend(_:)), keyEquivalent: "")
+    restartItem.target = self
+    let logLevelItem = menu.addItem(withTitle: "Log Level", action: nil, keyEquivalent: "")
+    for level in [ShellLog.Level.debug, .info, .warn, .error] {
+      let item = logLevelMenu.addItem(withTitle: level.rawValue.capitalized, action: #selector(selectLogLevel(_:)), keyEquivalent: "")
+      item.target = self
+      item.representedObject = level.rawValue
+    }
+    logLevelItem.submenu = logLevelMenu
+    menu.addItem(NSMenuItem.separator())
+
+    menu.addItem(withTitle: "Quit Invest Log", action: #selector(NSApplication.terminate(_:)), keyEquivalent: "")
+
+    statusItem.menu = menu
+    setBackendRunning(false)
+  }
+
+  /// Updates the status line; the coloured dot mirrors the backend health.
+  func setBackendRunning(_ running: Bool) {
+    let dot = NSAttributedString(
+      string: "● ",
+      attributes: [.foregroundColor: running ? NSColor.systemGreen : NSColor.systemRed]
+    )
+    let label = NSAttributedString(
+      string: running ? "Backend running" : "Backend stopped",
+      attributes: [.foregroundColor: NSColor.labelColor]
+    )
+    let title = NSMutableAttributedString()
+    title.append(dot)
+    title.append(label)
+    backendItem.attributedTitle = title
+  }
+
+  /// Replaces the portfolio lines at the top of the menu; nil clears them.
+  func updateSummary(_ summary: PortfolioSummary?) {
+    summaryItems.forEach { menu.removeItem($0) }
+    summaryItems = []
+    guard let summary = summary, !summary.totals.isEmpty else { return }
+
+    var items: [NSMenuItem] = []
+    for total in summary.totals {
+      let pnlColor = total.pnl >= 0 ? NSColor.systemGreen : NSColor.systemRed
+      let title = NSMutableAttributedString(
+        string: PortfolioSummary.formatAmount(total.marketValue, currency: total.currency) + "  ",
+        attributes: [.font: NSFont.menuFont(ofSize: 0).bold]
+      )
+      title.append(NSAttributedString(
+        string: PortfolioSummary.formatAmount(total.pnl, currency: ""),
+        attributes: [.foregroundColor: pnlColor]
+      ))
+      items.append(disabledItem(title))
+    }
+    if !summary.topPositions.isEmpty {
+      items.append(NSMenuItem.separator())
+      for position in summary.topPositions {
+        var text = "\(position.displayName)  " + PortfolioSummary.formatAmount(position.marketValue, currency: position.currency)
+        if let percent = position.pnlPercent {
+          text += "  " + PortfolioSummary.formatPercent(percent)
+        }
+        items.append(disabledItem(NSAttributedString(string: text)))
+      }
+    }
+    items.append(NSMenuItem.separator())
+
+    for (index, item) in items.enumerated() {
+      menu.insertItem(item, at: index)
+    }
+    summaryItems = items
+  }
+
+  func menuWillOpen(_ menu: NSMenu) {
+    onMenuWillOpen?()
+  }
+
+  private func disabledItem(_ title: NSAttributedString) -> NSMenuItem {
+    let item = NSMenuItem(title: title.string, action: nil, keyEquivalent: "")
+    item.attributedTitle = title
+    item.isEnabled = false
+    return item
+  }
+
+  func setWindowVisible(_ visible: Bool) {
+    toggleWindowItem.title = visible ? "Hide Invest Log" : "Show Invest Log"
+  }
+
+  /// Presentation mode removes the item so nothing personal pops up on a shared screen.
+  func setHidden(_ hidden: Bool) {
+    statusItem.isVisible = !hidden
+  }
+
+  func setLogLevel(_ level: ShellLog.Level) {
+    logLevelMenu.items.forEach { $0.state = ($0.representedObject as? String) == level.rawValue ? .on : .off }
+  }
+
+  func setTickerVisible(_ visible: Bool) {
+    toggleTickerItem.title = visible ? "Hide Mini Ticker" : "Show Mini Ticker"
+  }
+
+  @objc private func toggleTicker(_ sender: Any?) {
+    onToggleTicker?()
+  }
+
+  @objc private func selectLogLevel(_ sender: NSMenuItem) {
+    guard let raw = sender.representedObject as? String, let level = ShellLog.Level(rawValue: raw) else { return }
+    onSelectLogLevel?(level)
+  }
+
+  @objc private func toggleWindow(_ sender: Any?) {
+    onToggleWindow?()
+  }
+
+  @objc private func restartBackend(_ sender: Any?) {
+    onRestartBackend?()
+  }
+}
+
+private extension NSFont {
+  var bold: NSFont {
+    return NSFontManager.shared.convert(self, toHaveTrait: .boldFontMask)
+  }
+}
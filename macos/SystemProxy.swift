@@ -0,0 +1,59 @@
+import CFNetwork
+import Foundation
+
+/// The backend fetches quotes and exchange rates with Go's HTTP client, which only reads
+/// proxies from HTTP_PROXY / HTTPS_PROXY / NO_PROXY. This turns the Mac's proxy settings
+/// (or an explicit override) into those variables when the shell starts the backend.
+///
+/// Override: `defaults write com.investlog.app HTTPProxy "http://proxy.corp:8080"`; an
+/// empty string forces a direct connection. Automatic proxy configuration (PAC) can't be
+/// expressed as variables, so with PAC only the override applies.
+enum SystemProxy {
+  static func environment(inherited: [String: String]) -> [String: String] {
+    // Variables the user exported themselves (e.g. launching from a shell) win.
+    let proxyKeys = ["HTTP_PROXY", "HTTPS_PROXY", "http_proxy", "https_proxy"]
+    if proxyKeys.contains(where: { inherited[$0] != nil }) {
+      return [:]
+    }
+
+    if let override = UserDefaults.standard.string(forKey: "HTTPProxy") {
+      guard !override.isEmpty else { return [:] }
+      return ["HTTP_PROXY": override, "HTTPS_PROXY": override, "NO_PROXY": noProxy(exceptions: [])]
+    }
+
+    guard let settings = CFNetworkCopySystemProxySettings()?.takeRetainedValue() as? [String: Any] else {
+      return [:]
+    }
+    var env: [String: String] = [:]
+    if let http = proxyURL(settings, enable: kCFNetworkProxiesHTTPEnable, host: kCFNetworkProxiesHTTPProxy, port: kCFNetworkProxiesHTTPPort) {
+      env["HTTP_PROXY"] = http
+    }
+    if let https = proxyURL(settings, enable: kCFNetworkProxiesHTTPSEnable, host: kCFNetworkProxiesHTTPSProxy, port: kCFNetworkProxiesHTTPSPort) {
+      env["HTTPS_PROXY"] = https
+    }
+    if (settings[kCFNetworkProxiesProxyAutoConfigEnable as String] as? Int) == 1 && env.isEmpty {
+      ShellLog.warn("system uses automatic proxy configuration; set the HTTPProxy default for quote fetching")
+    }
+    guard !env.isEmpty else { return [:] }
+    env["NO_PROXY"] = noProxy(exceptions: settings[kCFNetworkProxiesExceptionsList as String] as? [String] ?? [])
+    ShellLog.info("backend uses system proxy", ["http": env["HTTP_PROXY"] ?? "", "https": env["HTTPS_PROXY"] ?? ""])
+    return env
+  }
+
+  private static func proxyURL(_ settings: [String: Any], enable: CFString, host: CFString, port: CFString) -> String? {
+    guard (settings[enable as String] as? Int) == 1,
+          let host = settings[host as String] as? String, !host.isEmpty else {
+      return nil
+    }
+    let port = settings[port as String] as? Int
+    return "http://\(host)" + (port.map { ":\($0)" } ?? "")
+  }
+
+  /// Go matches NO_PROXY entries by domain suffix, so macOS's `*.example.com` becomes `.example.com`.
+  private static func noProxy(exceptions: [String]) -> String {
+    let entries = ["localhost", "127.0.0.1", "::1"] + exceptions.map {
+      $0.hasPrefix("*.") ? String($0.dropFirst()) : $0
+    }
+    return entries.joined(separator: ",")
+  }
+}
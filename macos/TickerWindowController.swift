@@ -0,0 +1,101 @@
+import AppKit
+
+/// Compact always-on-top panel showing portfolio totals and the largest positions.
+final class TickerWindowController: NSObject, NSWindowDelegate {
+  private let panel: NSPanel
+  private let stack = NSStackView()
+
+  var onClose: (() -> Void)?
+
+  var isVisible: Bool {
+    return panel.isVisible
+  }
+
+  override init() {
+    panel = NSPanel(
+      contentRect: NSRect(x: 0, y: 0, width: 260, height: 120),
+      styleMask: [.titled, .closable, .utilityWindow, .nonactivatingPanel, .hudWindow],
+      backing: .buffered,
+      defer: false
+    )
+    super.init()
+
+    panel.title = "Invest Log"
+    panel.level = .floating
+    panel.isFloatingPanel = true
+    panel.hidesOnDeactivate = false
+    panel.isReleasedWhenClosed = false
+    panel.isMovableByWindowBackground = true
+    panel.collectionBehavior = [.canJoinAllSpaces, .fullScreenAuxiliary]
+    panel.setFrameAutosaveName("MiniTicker")
+    panel.delegate = self
+
+    stack.orientation = .vertical
+    stack.alignment = .leading
+    stack.spacing = 4
+    stack.edgeInsets = NSEdgeInsets(top: 10, left: 12, bottom: 10, right: 12)
+    panel.contentView = stack
+
+    update(nil)
+  }
+
+  func show() {
+    if panel.frame.origin == .zero, let screen = NSScreen.main {
+      let visible = screen.visibleFrame
+      panel.setFrameTopLeftPoint(NSPoint(x: visible.maxX - panel.frame.width - 16, y: visible.maxY - 16))
+    }
+    panel.orderFrontRegardless()
+  }
+
+  func hide() {
+    panel.orderOut(nil)
+  }
+
+  func update(_ summary: PortfolioSummary?) {
+    stack.arrangedSubviews.forEach { $0.removeFromSuperview() }
+    guard let summary = summary, !summary.totals.isEmpty else {
+      stack.addArrangedSubview(label("Waiting for backend…", color: .secondaryLabelColor))
+      resize()
+      return
+    }
+
+    for total in summary.totals {
+      let value = PortfolioSummary.formatAmount(total.marketValue, currency: total.currency)
+      let pnl = PortfolioSummary.formatAmount(total.pnl, currency: "")
+      let row = NSStackView(views: [
+        label(value, font: .boldSystemFont(ofSize: 13)),
+        label(pnl, color: total.pnl >= 0 ? .systemGreen : .systemRed),
+      ])
+      row.spacing = 8
+      stack.addArrangedSubview(row)
+    }
+    for position in summary.topPositions {
+      var text = position.displayName
+      if let percent = position.pnlPercent {
+        text += "  " + PortfolioSummary.formatPercent(percent)
+      }
+      stack.addArrangedSubview(label(text, color: .secondaryLabelColor))
+    }
+    resize()
+  }
+
+  func windowWillClose(_ notification: Notification) {
+    onClose?()
+  }
+
+  private func resize() {
+    stack.layoutSubtreeIfNeeded()
+    let size = stack.fittingSize
+    let frame = panel.frameRect(forContentRect: NSRect(origin: .zero, size: NSSize(width: max(size.width, 220), height: size.height)))
+    var origin = panel.frame.origin
+    origin.y += panel.frame.height - frame.height
+    panel.setFrame(NSRect(origin: origin, size: frame.size), display: true)
+  }
+
+  private func label(_ text: String, font: NSFont = .systemFont(ofSize: 12), color: NSColor = .labelColor) -> NSTextField {
+    let field = NSTextField(labelWithString: text)
+    field.font = font
+    field.textColor = color
+    return field
+  }
+}
@@ -0,0 +1,40 @@
+import AppKit
+
+/// Remembers a window's frame separately for every display, so docking or undocking
+/// puts it back where it last was on that screen instead of keeping one global frame.
+enum WindowLayout {
+  /// Stable across reconnects, unlike the CGDirectDisplayID alone.
+  static func key(for screen: NSScreen?) -> String? {
+    guard let number = screen?.deviceDescription[NSDeviceDescriptionKey("NSScreenNumber")] as? NSNumber else {
+      return nil
+    }
+    let displayID = CGDirectDisplayID(number.uint32Value)
+    return "\(CGDisplayVendorNumber(displayID))-\(CGDisplayModelNumber(displayID))-\(CGDisplaySerialNumber(displayID))"
+  }
+
+  static func save(_ window: NSWindow) {
+    guard let identifier = window.identifier?.rawValue,
+          !window.styleMask.contains(.fullScreen),
+          let screenKey = key(for: window.screen) else { return }
+    UserDefaults.standard.set(NSStringFromRect(window.frame), forKey: "WindowFrame.\(identifier).\(screenKey)")
+    UserDefaults.standard.set(screenKey, forKey: "WindowScreen.\(identifier)")
+  }
+
+  /// Restores the frame saved for `screen`, or for the display the window was last on
+  /// when that display is still connected. Returns false when nothing applied.
+  @discardableResult
+  static func restore(_ window: NSWindow, on screen: NSScreen? = nil) -> Bool {
+    guard let identifier = window.identifier?.rawValue else { return false }
+    let target = screen ?? UserDefaults.standard.string(forKey: "WindowScreen.\(identifier)").flatMap { saved in
+      NSScreen.screens.first { key(for: $0) == saved }
+    }
+    guard let target = target, let screenKey = key(for: target),
+          let saved = UserDefaults.standard.string(forKey: "WindowFrame.\(identifier).\(screenKey)") else {
+      return false
+    }
+    let frame = NSRectFromString(saved)
+    guard frame.width > 0, frame.height > 0, target.visibleFrame.intersects(frame) else { return false }
+    window.setFrame(frame, display: true)
+    return true
+  }
+}
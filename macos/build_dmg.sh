@@ -20,13 +20,19 @@ fi
 
 mkdir -p "$OUT_DIR"
 
-echo "Building backend (darwin/arm64)..."
+# The shell picks the backend matching the Mac's architecture at launch (BackendBinary.swift).
+ARCHS=(arm64 x86_64)
 BACKEND_BUILD_DIR="$OUT_DIR/backend"
 mkdir -p "$BACKEND_BUILD_DIR"
-(
-  cd "$REPO_DIR/go-backend"
-  GOOS=darwin GOARCH=arm64 go build -o "$BACKEND_BUILD_DIR/invest-log-backend" ./cmd/server
-)
+for arch in "${ARCHS[@]}"; do
+  echo "Building backend (darwin/$arch)..."
+  goarch="$arch"
+  [[ "$arch" == "x86_64" ]] && goarch="amd64"
+  (
+    cd "$REPO_DIR/go-backend"
+    GOOS=darwin GOARCH="$goarch" go build -o "$BACKEND_BUILD_DIR/invest-log-backend-$arch" ./cmd/server
+  )
+done
 
 APP_DIR="$OUT_DIR/${APP_NAME}.app"
 CONTENTS_DIR="$APP_DIR/Contents"
@@ -38,16 +44,32 @@ mkdir -p "$MACOS_DIR" "$RESOURCES_DIR"
 
 echo "Copying resources..."
 cp -R "$REPO_DIR/static" "$RESOURCES_DIR/static"
-cp "$BACKEND_BUILD_DIR/invest-log-backend" "$RESOURCES_DIR/invest-log-backend"
-cp "$ROOT_DIR/loading.html" "$RESOURCES_DIR/loading.html"
+# The loader's CSP only admits its own inline script, pinned by hash at build time.
+LOADER_SCRIPT_HASH="$(perl -0777 -ne 'print $1 if /<script>(.*?)<\/script>/s' "$ROOT_DIR/loading.html" | openssl dgst -sha256 -binary | base64)"
+sed "s|__LOADER_SCRIPT_SHA256__|$LOADER_SCRIPT_HASH|" "$ROOT_DIR/loading.html" > "$RESOURCES_DIR/loading.html"
 cp "$ROOT_DIR/AppIcon.icns" "$RESOURCES_DIR/AppIcon.icns"
-chmod +x "$RESOURCES_DIR/invest-log-backend"
+cp "$ROOT_DIR/InvestLog.sdef" "$RESOURCES_DIR/InvestLog.sdef"
+for arch in "${ARCHS[@]}"; do
+  backend="$RESOURCES_DIR/invest-log-backend-$arch"
+  cp "$BACKEND_BUILD_DIR/invest-log-backend-$arch" "$backend"
+  chmod +x "$backend"
+  # Recorded so the app's self-test can tell a damaged or replaced backend apart.
+  shasum -a 256 "$backend" | awk '{print $1}' > "$backend.sha256"
+done
 
 echo "Compiling macOS app..."
-swiftc "$ROOT_DIR/main.swift" \
-  -o "$MACOS_DIR/$APP_NAME" \
-  -framework AppKit \
-  -framework WebKit
+SHELL_SLICES=()
+for arch in "${ARCHS[@]}"; do
+  swiftc "$ROOT_DIR"/*.swift \
+    -target "$arch-apple-macos12.0" \
+    -o "$OUT_DIR/$APP_NAME-$arch" \
+    -framework AppKit \
+    -framework Carbon \
+    -framework WebKit
+  SHELL_SLICES+=("$OUT_DIR/$APP_NAME-$arch")
+done
+lipo -create "${SHELL_SLICES[@]}" -output "$MACOS_DIR/$APP_NAME"
+rm -f "${SHELL_SLICES[@]}"
 
 echo "Writing Info.plist..."
 cat > "$CONTENTS_DIR/Info.plist" <<EOF
@@ -71,10 +93,40 @@ cat > "$CONTENTS_DIR/Info.plist" <<EOF
   <string>1.0.0</string>
   <key>CFBundleVersion</key>
   <string>1</string>
+  <key>CFBundleURLTypes</key>
+  <array>
+    <dict>
+      <key>CFBundleURLName</key>
+      <string>${APP_BUNDLE_ID}</string>
+      <key>CFBundleURLSchemes</key>
+      <array>
+        <string>investlog</string>
+      </array>
+    </dict>
+  </array>
+  <key>CFBundleDocumentTypes</key>
+  <array>
+    <dict>
+      <key>CFBundleTypeName</key>
+      <string>Invest Log Database</string>
+      <key>CFBundleTypeRole</key>
+      <string>Editor</string>
+      <key>CFBundleTypeExtensions</key>
+      <array>
+        <string>db</string>
+      </array>
+      <key>LSHandlerRank</key>
+      <string>Alternate</string>
+    </dict>
+  </array>
   <key>LSMinimumSystemVersion</key>
   <string>12.0</string>
   <key>NSHighResolutionCapable</key>
   <true/>
+  <key>NSAppleScriptEnabled</key>
+  <true/>
+  <key>OSAScriptingDefinition</key>
+  <string>InvestLog.sdef</string>
   <key>NSAppTransportSecurity</key>
   <dict>
     <key>NSAllowsArbitraryLoads</key>
@@ -91,8 +143,8 @@ mkdir -p "$DMG_STAGE"
 cp -R "$APP_DIR" "$DMG_STAGE/"
 ln -s /Applications "$DMG_STAGE/Applications"
 
-RW_DMG_PATH="$OUT_DIR/${APP_NAME}-macOS-arm64.tmp.dmg"
-DMG_PATH="$OUT_DIR/${APP_NAME}-macOS-arm64.dmg"
+RW_DMG_PATH="$OUT_DIR/${APP_NAME}-macOS-universal.tmp.dmg"
+DMG_PATH="$OUT_DIR/${APP_NAME}-macOS-universal.dmg"
 ATTACHED_DEVICE=""
 ATTACHED_VOLUME_NAME=""
 
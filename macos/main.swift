@@ -1,4 +1,5 @@
 import AppKit
+import UniformTypeIdentifiers
 import WebKit
 
 @available(macOS 11.0, *)
@@ -10,37 +11,423 @@ private func clearWebViewWebsiteData() {
   }
 }
 
-class AppDelegate: NSObject, NSApplicationDelegate {
+struct StorageInfo: Decodable {
+  let dbPath: String
+  let dataDir: String
+  let canSwitch: Bool
+  let switchReason: String?
+
+  enum CodingKeys: String, CodingKey {
+    case dbPath = "db_path"
+    case dataDir = "data_dir"
+    case canSwitch = "can_switch"
+    case switchReason = "switch_reason"
+  }
+}
+
+/// Steps shown by the loader while the backend comes up.
+enum StartupStage: Int, CaseIterable {
+  case locating = 1
+  case spawning
+  case waiting
+  case navigating
+
+  var label: String {
+    switch self {
+    case .locating: return "Locating local engine…"
+    case .spawning: return "Starting local engine…"
+    case .waiting: return "Waiting for local engine…"
+    case .navigating: return "Opening Invest Log…"
+    }
+  }
+}
+
+/// Why startup stopped. The loader page maps `code` to localized text and shows `detail` underneath.
+struct StartupError {
+  enum Code: String {
+    case resourcesMissing = "resources_missing"
+    case backendMissing = "backend_missing"
+    case spawnFailed = "spawn_failed"
+    case backendExited = "backend_exited"
+    case timeout
+    // Refined from the backend's output by BackendOutput.classify().
+    case portInUse = "port_in_use"
+    case databaseLocked = "db_locked"
+    case databaseCorrupt = "db_corrupt"
+    case permissionDenied = "permission_denied"
+    case diskFull = "disk_full"
+  }
+
+  let code: Code
+  let detail: String
+  let stage: StartupStage
+
+  var payload: [String: Any] {
+    return ["code": code.rawValue, "detail": detail, "stage": stage.rawValue]
+  }
+}
+
+class AppDelegate: NSObject, NSApplicationDelegate, NSWindowDelegate, WKNavigationDelegate, WKUIDelegate,
+  WKScriptMessageHandler, WKDownloadDelegate {
   private var window: NSWindow!
   private var webView: WKWebView!
+  private var splashWindow: NSWindow?
   private var backendProcess: Process?
+  private var backendOutput: BackendOutput?
+  private var statusBar: StatusBarController?
+  private var secondaryWindows: [NSWindow] = []
+  private var backendReady = false
+  private var toggleHotKey: GlobalHotKey?
+  private var quickAddHotKey: GlobalHotKey?
+  private var quickAddPanel: NSPanel?
+  private var quickAddObservation: NSKeyValueObservation?
+  private let commandPalette = CommandPalette()
+  private lazy var logViewer = LogViewerWindowController()
+  private let healthWindow = HealthWindowController()
+  private let metricsServer = MetricsServer()
+  private let appLock = AppLock()
+  private let privacyShield = PrivacyShield()
+  private let credentialsHandler = CredentialsMessageHandler()
+  private var loadDeferredByLock = false
+  private var backendRestarts = 0
+  private var backendStartedAt: Date?
+  private var backendLogsDirectory: URL?
+  private var routeObservation: NSKeyValueObservation?
+  private var didRestoreRoute = false
+  private var presentationMode = false
+  private var summaryTimer: Timer?
+  private var ticker: TickerWindowController?
+  private var tickerTimer: Timer?
+  private var spotlightTimer: Timer?
+  private var pendingRoute: String?
+  private var pendingFiles: [URL] = []
+  private var mainWindowClosed = false
+  private var startupStage = StartupStage.locating
+  private var startupFailure: StartupError?
+  private var startupStartedAt = Date()
+  private let operations = OperationTracker()
+  private let timeline = StartupTimeline()
+  private let logLevelMenu = NSMenu(title: "Log Level")
+  private var quitWhenIdle = false
+  private var sleepAssertion: NSObjectProtocol?
+  private var pendingShare: (pdfURL: URL, page: WKWebView)?
+
+  /// `open -a InvestLog --args --debug`: inspectable web views, debug logging in shell and
+  /// backend, no silent engine restarts, and a stamped title so screenshots show the mode.
+  private let debugMode = CommandLine.arguments.contains("--debug")
 
   private let host = "127.0.0.1"
+  private var backendTLSMenuItem: NSMenuItem?
+  private var lanAccessMenuItem: NSMenuItem?
   private let port = 8000
   private let maxAttempts = 80
 
   func applicationDidFinishLaunching(_ notification: Notification) {
+    CrashReporter.install()
+    ShellLog.minimumLevel = debugMode ? .debug : currentLogLevel
+    timeline.mark(.launched)
+    ShellLog.info("shell starting", [
+      "version": Bundle.main.object(forInfoDictionaryKey: "CFBundleShortVersionString") ?? "unknown",
+      "os": ProcessInfo.processInfo.operatingSystemVersionString,
+      "debug": debugMode,
+    ])
+    // The backend takes the longest to come up, so it starts before any window or webview
+    // exists; the loader picks up the current stage once it has loaded.
+    runStartupPipeline()
     if #available(macOS 11.0, *) {
       clearWebViewWebsiteData()
     }
+    operations.onChange = { [weak self] in self?.operationsDidChange() }
     setupMenu()
+    commandPalette.commandsProvider = { [weak self] in self?.paletteCommands() ?? [] }
+    healthWindow.stateProvider = { [unowned self] in self.healthState() }
+    metricsServer.snapshotProvider = { [weak self] in self?.metricsSnapshot() }
+    if UserDefaults.standard.bool(forKey: "MetricsEnabled") {
+      metricsServer.start(port: metricsPort)
+    }
     setupWindow()
-    loadLoadingScreen()
-    startBackend()
-    waitForServer(attempt: 0)
+    timeline.mark(.windowReady)
+    setupAppLock()
+    setupPrivacyShield()
+    NSWorkspace.shared.notificationCenter.addObserver(
+      self, selector: #selector(systemDidWake(_:)), name: NSWorkspace.didWakeNotification, object: nil
+    )
+    setupStatusBar()
+    registerGlobalHotKey()
+    NotificationCenter.default.addObserver(
+      self,
+      selector: #selector(screenParametersDidChange(_:)),
+      name: NSApplication.didChangeScreenParametersNotification,
+      object: nil
+    )
+    showSplash()
+    CrashReporter.offerPreviousReport { [weak self] in self?.copyDiagnostics() }
+  }
+
+  func application(_ application: NSApplication, open urls: [URL]) {
+    for url in urls {
+      if url.isFileURL {
+        openDatabaseFile(url)
+        continue
+      }
+      guard let route = DeepLink.route(from: url) else { continue }
+      if backendReady {
+        navigate(to: route)
+        NSApp.activate(ignoringOtherApps: true)
+      } else {
+        // Cold launch from a link: land there once the backend is up.
+        pendingRoute = route
+      }
+    }
+  }
+
+  /// Spotlight results carry an `investlog://` link, so they open like any other link.
+  func application(_ application: NSApplication, continue userActivity: NSUserActivity,
+                   restorationHandler: @escaping ([NSUserActivityRestoring]) -> Void) -> Bool {
+    guard let link = SpotlightIndex.link(for: userActivity) else { return false }
+    self.application(application, open: [link])
+    return true
+  }
+
+  /// Switches to a double-clicked database, copying it into the data folder first when
+  /// it lives elsewhere (the backend only switches between files in its data dir).
+  /// Paths given on the command line (`InvestLog ~/backup.db`) arrive here too, through
+  /// AppKit's open-file handling; before the backend is healthy they wait in pendingFiles.
+  private func openDatabaseFile(_ fileURL: URL) {
+    guard fileURL.pathExtension.lowercased() == "db" else {
+      ShellLog.warn("ignored file of unsupported type", ["extension": fileURL.pathExtension])
+      showError("Invest Log can only open database (.db) files; \(fileURL.lastPathComponent) was not opened.")
+      return
+    }
+    guard backendReady else {
+      if !pendingFiles.contains(fileURL) {
+        pendingFiles.append(fileURL)
+      }
+      return
+    }
+    fetchStorageInfo { [weak self] info in
+      guard let self = self else { return }
+      guard let info = info else {
+        self.showError("Unable to reach the backend to open \(fileURL.lastPathComponent).")
+        return
+      }
+      guard info.canSwitch else {
+        self.showError(info.switchReason ?? "Switching databases is disabled.")
+        return
+      }
+
+      let dataDir = URL(fileURLWithPath: info.dataDir, isDirectory: true).standardizedFileURL
+      var target = fileURL.standardizedFileURL
+      if target.deletingLastPathComponent().path != dataDir.path {
+        let alert = NSAlert()
+        alert.messageText = "Open \(fileURL.lastPathComponent)?"
+        alert.informativeText = "Invest Log will copy this database into its data folder (\(dataDir.path)) and switch to it. The original file is left untouched."
+        alert.addButton(withTitle: "Copy and Open")
+        alert.addButton(withTitle: "Cancel")
+        guard alert.runModal() == .alertFirstButtonReturn else { return }
+
+        target = self.uniqueDestination(for: fileURL.lastPathComponent, in: dataDir)
+        let operation = self.operations.begin("Copying \(fileURL.lastPathComponent)")
+        let destination = target
+        DispatchQueue.global(qos: .userInitiated).async {
+          do {
+            try FileManager.default.copyItem(at: fileURL, to: destination)
+            DispatchQueue.main.async {
+              self.switchStorage(to: destination.lastPathComponent)
+              self.operations.end(operation)
+            }
+          } catch {
+            DispatchQueue.main.async {
+              self.operations.end(operation)
+              self.showError("Unable to copy the database. \(error.localizedDescription)")
+            }
+          }
+        }
+        return
+      }
+      self.switchStorage(to: target.lastPathComponent)
+    }
+  }
+
+  private func uniqueDestination(for fileName: String, in directory: URL) -> URL {
+    let base = (fileName as NSString).deletingPathExtension
+    let ext = (fileName as NSString).pathExtension
+    var candidate = directory.appendingPathComponent(fileName)
+    var index = 2
+    while FileManager.default.fileExists(atPath: candidate.path) {
+      candidate = directory.appendingPathComponent("\(base)-\(index)").appendingPathExtension(ext)
+      index += 1
+    }
+    return candidate
+  }
+
+  private func switchStorage(to dbName: String) {
+    let url = URL(string: "\(BackendTLS.scheme)://\(host):\(port)/api/storage/switch")!
+    var request = URLRequest(url: url)
+    request.httpMethod = "POST"
+    request.setValue("application/json", forHTTPHeaderField: "Content-Type")
+    request.httpBody = try? JSONSerialization.data(withJSONObject: ["db_name": dbName])
+    request.timeoutInterval = 10.0
+
+    let operation = operations.begin("Switching to \(dbName)")
+    BackendTLS.session.dataTask(with: request) { [weak self] data, response, error in
+      DispatchQueue.main.async {
+        guard let self = self else { return }
+        self.operations.end(operation)
+        if let http = response as? HTTPURLResponse, http.statusCode == 200 {
+          self.webView.reload()
+          self.reloadSecondaryWindows()
+          self.refreshPortfolioSummary()
+          return
+        }
+        var message = error?.localizedDescription ?? "Unknown error."
+        if let data = data,
+           let payload = try? JSONSerialization.jsonObject(with: data) as? [String: Any],
+           let backendError = payload["error"] as? String {
+          message = backendError
+        }
+        self.showError("Unable to switch to \(dbName). \(message)")
+      }
+    }.resume()
+  }
+
+  func applicationShouldTerminate(_ sender: NSApplication) -> NSApplication.TerminateReply {
+    guard operations.isBusy else { return .terminateNow }
+
+    let alert = NSAlert()
+    alert.messageText = "Invest Log is still working"
+    alert.informativeText = "\(operations.labels.joined(separator: ", ")) is in progress. Quitting now may leave it incomplete."
+    alert.addButton(withTitle: "Wait and Quit")
+    alert.addButton(withTitle: "Quit Anyway")
+    alert.addButton(withTitle: "Cancel")
+    switch alert.runModal() {
+    case .alertFirstButtonReturn:
+      quitWhenIdle = true
+      return .terminateCancel
+    case .alertSecondButtonReturn:
+      return .terminateNow
+    default:
+      return .terminateCancel
+    }
+  }
+
+  private func operationsDidChange() {
+    // Hold a power assertion while work is in flight so idle sleep or App Nap
+    // can't stall it halfway; ProcessInfo maps this to IOPMAssertion on macOS.
+    if operations.isBusy && sleepAssertion == nil {
+      sleepAssertion = ProcessInfo.processInfo.beginActivity(
+        options: [.userInitiated, .idleSystemSleepDisabled],
+        reason: operations.labels.joined(separator: ", ")
+      )
+    } else if !operations.isBusy, let assertion = sleepAssertion {
+      ProcessInfo.processInfo.endActivity(assertion)
+      sleepAssertion = nil
+    }
+
+    if quitWhenIdle && !operations.isBusy {
+      quitWhenIdle = false
+      NSApp.terminate(nil)
+    }
   }
 
   func applicationWillTerminate(_ notification: Notification) {
+    ShellLog.info("shell exiting")
     backendProcess?.terminate()
   }
 
   func applicationShouldTerminateAfterLastWindowClosed(_ sender: NSApplication) -> Bool {
+    // Windows hidden from the menu bar or hot key aren't closed, so closing the
+    // mini ticker or a report window alone must not quit the app.
+    return mainWindowClosed
+  }
+
+  func applicationDidResignActive(_ notification: Notification) {
+    updatePrivacyShield()
+  }
+
+  func applicationDidBecomeActive(_ notification: Notification) {
+    appLock.noteActivity()
+    updatePrivacyShield()
+    if appLock.isLocked && window.isVisible && !appLock.promptDismissed {
+      appLock.unlock()
+    }
+  }
+
+  /// Clicking the Dock icon brings back a window that was hidden to the menu bar.
+  func applicationShouldHandleReopen(_ sender: NSApplication, hasVisibleWindows flag: Bool) -> Bool {
+    if !window.isVisible {
+      showMainWindow()
+    }
+    verifyBackendHealth()
     return true
   }
 
+  /// Restarts the engine if it died or stopped answering while the app sat in the background.
+  /// `onHealthy` runs only when the running engine still answers; a restart reloads the pages itself.
+  private func verifyBackendHealth(onHealthy: (() -> Void)? = nil) {
+    guard backendReady else { return }
+    guard let process = backendProcess, process.isRunning else {
+      recoverBackend(.backendExited, "Exit code \(backendProcess?.terminationStatus ?? -1)")
+      return
+    }
+    var request = URLRequest(url: URL(string: "\(BackendTLS.scheme)://\(host):\(port)/api/health")!)
+    request.timeoutInterval = 2.0
+    BackendTLS.session.dataTask(with: request) { [weak self] _, response, _ in
+      let healthy = (response as? HTTPURLResponse)?.statusCode == 200
+      DispatchQueue.main.async {
+        guard let self = self, self.backendReady else { return }
+        guard healthy else {
+          self.recoverBackend(.timeout, "No response from \(self.host):\(self.port)/api/health")
+          return
+        }
+        onHealthy?()
+      }
+    }.resume()
+  }
+
+  /// After a night asleep the page may hold stale data or failed requests. The network
+  /// stack needs a moment after wake, so the check waits a few seconds before running.
+  @objc private func systemDidWake(_ notification: Notification) {
+    ShellLog.info("system woke")
+    DispatchQueue.main.asyncAfter(deadline: .now() + 3.0) { [weak self] in
+      self?.verifyBackendHealth {
+        guard let self = self else { return }
+        self.publishShellEvent("system-wake")
+        self.refreshPortfolioSummary()
+      }
+    }
+  }
+
+  /// Restarts quietly, except in debug mode where the failure is left on screen to inspect.
+  private func recoverBackend(_ code: StartupError.Code, _ detail: String) {
+    if debugMode {
+      reportBackendFailure(code, detail)
+    } else {
+      restartBackend()
+    }
+  }
+
   private func setupWindow() {
     let config = WKWebViewConfiguration()
-    webView = WKWebView(frame: .zero, configuration: config)
+    // Release builds keep Web Inspector and remote debugging off; only --debug turns them on.
+    config.preferences.setValue(debugMode, forKey: "developerExtrasEnabled")
+    config.preferences.javaScriptCanOpenWindowsAutomatically = false
+    config.userContentController.add(self, name: "loader")
+    config.userContentController.add(self, name: "shellContext")
+    credentialsHandler.isAllowedOrigin = { [weak self] url in self?.isBackendURL(url) ?? false }
+    config.userContentController.addScriptMessageHandler(credentialsHandler, contentWorld: .page, name: "credentials")
+    installUserScripts(into: config.userContentController)
+    let shellWebView = ShellWebView(frame: .zero, configuration: config)
+    shellWebView.menuBuilder = { [weak self] view, menu in
+      self?.buildContextMenu(for: view, into: menu)
+    }
+    webView = shellWebView
+    if #available(macOS 13.3, *) {
+      webView.isInspectable = debugMode
+    }
+    // Let the window background show through until the first page paints, so
+    // dark mode doesn't flash white while the loader is being read from disk.
+    webView.setValue(false, forKey: "drawsBackground")
 
     window = NSWindow(
       contentRect: NSRect(x: 0, y: 0, width: 1200, height: 800),
@@ -48,112 +435,1788 @@ class AppDelegate: NSObject, NSApplicationDelegate {
       backing: .buffered,
       defer: false
     )
+    window.isReleasedWhenClosed = false
+    window.identifier = NSUserInterfaceItemIdentifier("main")
     window.center()
-    window.title = "Invest Log"
-    window.contentView = webView
-    window.makeKeyAndOrderFront(nil)
+    WindowLayout.restore(window)
+    window.title = debugMode ? "Invest Log [debug]" : "Invest Log"
+    applyTitleBarStyle(to: window)
+    window.contentView = vibrancyEnabled ? makeVibrancyContainer(hosting: webView) : webView
+    restoreZoom(for: window, webView: webView)
+    window.delegate = self
+    // Stays hidden until the first backend page has loaded; see revealMainWindow().
+    webView.navigationDelegate = self
+    webView.uiDelegate = self
+    routeObservation = webView.observe(\.url, options: [.new]) { [weak self] observed, _ in
+      guard let self = self, let url = observed.url, self.isBackendURL(url),
+            let fragment = url.fragment, fragment.hasPrefix("/") else { return }
+      UserDefaults.standard.set(self.strippingRestoreFlag(fragment), forKey: "LastRoute")
+    }
   }
 
-  private func loadLoadingScreen() {
-    if let url = Bundle.main.url(forResource: "loading", withExtension: "html") {
-      webView.loadFileURL(url, allowingReadAccessTo: url.deletingLastPathComponent())
+  /// Scripts injected into every backend page; rebuilt whenever a shell setting they read changes.
+  private func installUserScripts(into controller: WKUserContentController) {
+    controller.removeAllUserScripts()
+    let inset = titleBarOverlay ? 28 : 0
+    let source = """
+      document.documentElement.style.setProperty('--shell-titlebar-inset', '\(inset)px');
+      document.documentElement.classList.toggle('shell-vibrancy', \(vibrancyEnabled));
+      """
+    controller.addUserScript(WKUserScript(source: source, injectionTime: .atDocumentStart, forMainFrameOnly: true))
+  }
+
+  /// "overlay" lets the page run under a transparent title bar; anything else is standard.
+  private var titleBarOverlay: Bool {
+    return UserDefaults.standard.string(forKey: "TitleBarStyle") == "overlay"
+  }
+
+  private func applyTitleBarStyle(to target: NSWindow) {
+    if titleBarOverlay {
+      target.styleMask.insert(.fullSizeContentView)
+      target.titlebarAppearsTransparent = true
+      target.titleVisibility = .hidden
     } else {
-      webView.loadHTMLString("<!doctype html><html><body><p>Loading…</p></body></html>", baseURL: nil)
+      target.styleMask.remove(.fullSizeContentView)
+      target.titlebarAppearsTransparent = false
+      target.titleVisibility = .visible
     }
   }
 
-  private func startBackend() {
-    guard let resourcePath = Bundle.main.resourcePath else {
-      showFatalError("Missing app resources.")
-      return
+  @objc private func selectTitleBarStyle(_ sender: NSMenuItem) {
+    guard let style = sender.representedObject as? String else { return }
+    UserDefaults.standard.set(style, forKey: "TitleBarStyle")
+    sender.menu?.items.forEach { $0.state = ($0.representedObject as? String) == style ? .on : .off }
+
+    applyTitleBarStyle(to: window)
+    installUserScripts(into: webView.configuration.userContentController)
+    let inset = titleBarOverlay ? 28 : 0
+    webView.callShellScript(
+      "document.documentElement.style.setProperty('--shell-titlebar-inset', `${inset}px`)",
+      ["inset": inset]
+    )
+  }
+
+  /// Opt-in translucent background; pages see `html.shell-vibrancy` and drop their opaque fill.
+  private var vibrancyEnabled: Bool {
+    return UserDefaults.standard.bool(forKey: "WindowVibrancy")
+  }
+
+  private func makeVibrancyContainer(hosting content: NSView) -> NSView {
+    let background = NSVisualEffectView()
+    background.material = .sidebar
+    background.blendingMode = .behindWindow
+    background.state = .followsWindowActiveState
+    content.frame = background.bounds
+    content.autoresizingMask = [.width, .height]
+    background.addSubview(content)
+    return background
+  }
+
+  /// The web view of a shell window, whether it is the content view or sits inside the vibrancy container.
+  private func hostedWebView(in target: NSWindow) -> WKWebView? {
+    if let direct = target.contentView as? WKWebView {
+      return direct
     }
+    return target.contentView?.subviews.compactMap { $0 as? WKWebView }.first
+  }
 
-    let backendURL = URL(fileURLWithPath: resourcePath).appendingPathComponent("invest-log-backend")
-    let webDirURL = URL(fileURLWithPath: resourcePath).appendingPathComponent("static")
+  @objc private func toggleVibrancy(_ sender: NSMenuItem) {
+    let enabled = !vibrancyEnabled
+    UserDefaults.standard.set(enabled, forKey: "WindowVibrancy")
+    sender.state = enabled ? .on : .off
 
-    let process = Process()
-    process.executableURL = backendURL
-    process.arguments = [
-      "--host", host,
-      "--port", "\(port)",
-      "--web-dir", webDirURL.path
-    ]
-    process.currentDirectoryURL = URL(fileURLWithPath: resourcePath)
-    var env = ProcessInfo.processInfo.environment
-    env["INVEST_LOG_PARENT_WATCH"] = "1"
-    process.environment = env
+    window.contentView = nil
+    webView.removeFromSuperview()
+    window.contentView = enabled ? makeVibrancyContainer(hosting: webView) : webView
+    installUserScripts(into: webView.configuration.userContentController)
+    webView.callShellScript("document.documentElement.classList.toggle('shell-vibrancy', enabled)", ["enabled": enabled])
+  }
 
-    do {
-      try process.run()
-      backendProcess = process
-    } catch {
-      showFatalError("Unable to start backend. \(error.localizedDescription)")
+  /// Small frameless window that hosts the loader while the main window is hidden.
+  private func showSplash() {
+    let splashConfig = WKWebViewConfiguration()
+    splashConfig.userContentController.add(self, name: "loader")
+    let splashWebView = WKWebView(frame: .zero, configuration: splashConfig)
+    splashWebView.setValue(false, forKey: "drawsBackground")
+    splashWebView.navigationDelegate = self
+
+    let splash = NSWindow(
+      contentRect: NSRect(x: 0, y: 0, width: 420, height: 340),
+      styleMask: [.borderless],
+      backing: .buffered,
+      defer: false
+    )
+    splash.isReleasedWhenClosed = false
+    splash.isMovableByWindowBackground = true
+    splash.hasShadow = true
+    splash.contentView = splashWebView
+    splash.center()
+    loadLoadingScreen(in: splashWebView)
+    splash.makeKeyAndOrderFront(nil)
+    splashWindow = splash
+  }
+
+  private func revealMainWindow() {
+    guard let splash = splashWindow else { return }
+    splashWindow = nil
+    window.makeKeyAndOrderFront(nil)
+    NSApp.activate(ignoringOtherApps: true)
+    splash.orderOut(nil)
+    splash.close()
+    statusBar?.setWindowVisible(true)
+  }
+
+  func webView(_ webView: WKWebView, didFinish navigation: WKNavigation!) {
+    if webView.url?.isFileURL == true {
+      // The loader just (re)loaded; replay the current stage into it.
+      renderLoaderState()
+    } else if webView.url.map(isBackendURL) == true {
+      if webView === self.webView {
+        timeline.mark(.firstPageLoaded)
+      }
+      revealMainWindow()
     }
   }
 
-  private func waitForServer(attempt: Int) {
-    let url = URL(string: "http://\(host):\(port)/api/health")!
-    var request = URLRequest(url: url)
-    request.timeoutInterval = 1.0
+  /// Webviews only ever show the backend or the bundled loader, in any frame. Links the user
+  /// clicks to anywhere else open in the default browser; other foreign navigations, including
+  /// redirects and any other file:// URL, are dropped and logged.
+  func webView(
+    _ webView: WKWebView,
+    decidePolicyFor navigationAction: WKNavigationAction,
+    decisionHandler: @escaping (WKNavigationActionPolicy) -> Void
+  ) {
+    guard let url = navigationAction.request.url else {
+      decisionHandler(.cancel)
+      return
+    }
+    // Files the app builds in the page (e.g. the JSON data export) arrive as blob: downloads.
+    if navigationAction.shouldPerformDownload, url.scheme == "blob", webView.url.map(isBackendURL) == true {
+      decisionHandler(.download)
+      return
+    }
+    if isAllowedNavigation(url) {
+      decisionHandler(.allow)
+      return
+    }
+    if navigationAction.navigationType == .linkActivated && !url.isFileURL {
+      openExternally(url)
+    } else {
+      ShellLog.warn("blocked navigation", [
+        "url": url.absoluteString,
+        "type": navigationAction.navigationType.rawValue,
+        "main_frame": navigationAction.targetFrame?.isMainFrame ?? true,
+        "from": webView.url?.absoluteString ?? "",
+      ])
+    }
+    decisionHandler(.cancel)
+  }
 
-    URLSession.shared.dataTask(with: request) { [weak self] _, response, _ in
-      guard let self = self else { return }
-      if let http = response as? HTTPURLResponse, http.statusCode == 200 {
-        DispatchQueue.main.async { self.loadApp() }
+  func webView(_ webView: WKWebView, navigationAction: WKNavigationAction, didBecome download: WKDownload) {
+    download.delegate = self
+  }
+
+  /// Asks where to save, as a browser would; the save panel has already confirmed any replacement.
+  func download(
+    _ download: WKDownload,
+    decideDestinationUsing response: URLResponse,
+    suggestedFilename: String,
+    completionHandler: @escaping (URL?) -> Void
+  ) {
+    let panel = NSSavePanel()
+    panel.nameFieldStringValue = suggestedFilename
+    panel.canCreateDirectories = true
+    panel.begin { result in
+      guard result == .OK, let destination = panel.url else {
+        completionHandler(nil)
         return
       }
-      if attempt < self.maxAttempts {
-        DispatchQueue.main.asyncAfter(deadline: .now() + 0.25) {
-          self.waitForServer(attempt: attempt + 1)
-        }
+      try? FileManager.default.removeItem(at: destination)
+      completionHandler(destination)
+    }
+  }
+
+  func downloadDidFinish(_ download: WKDownload) {
+    ShellLog.info("download saved")
+  }
+
+  func download(_ download: WKDownload, didFailWithError error: Error, resumeData: Data?) {
+    ShellLog.warn("download failed", ["error": error.localizedDescription])
+    showError("The file could not be saved: \(error.localizedDescription)")
+  }
+
+  /// Redirects normally come through decidePolicyFor as well; this catches any that don't.
+  func webView(_ webView: WKWebView, didReceiveServerRedirectForProvisionalNavigation navigation: WKNavigation!) {
+    guard let url = webView.url, !isAllowedNavigation(url) else { return }
+    ShellLog.warn("blocked redirect", ["url": url.absoluteString])
+    webView.stopLoading()
+  }
+
+  private func isAllowedNavigation(_ url: URL) -> Bool {
+    return isBackendURL(url) || isLoaderURL(url) || url.absoluteString == "about:blank"
+  }
+
+  /// The bundled loader is the only local page a web view may open.
+  private func isLoaderURL(_ url: URL) -> Bool {
+    guard url.isFileURL, let loader = Bundle.main.url(forResource: "loading", withExtension: "html") else { return false }
+    return url.standardizedFileURL.resolvingSymlinksInPath().path == loader.standardizedFileURL.resolvingSymlinksInPath().path
+  }
+
+  /// `target="_blank"` links (e.g. sources in AI answers) would otherwise do nothing.
+  func webView(
+    _ webView: WKWebView,
+    createWebViewWith configuration: WKWebViewConfiguration,
+    for navigationAction: WKNavigationAction,
+    windowFeatures: WKWindowFeatures
+  ) -> WKWebView? {
+    if let url = navigationAction.request.url {
+      if isBackendURL(url) {
+        webView.load(URLRequest(url: url))
       } else {
-        DispatchQueue.main.async { self.loadApp() }
+        openExternally(url)
       }
-    }.resume()
+    }
+    return nil
   }
 
-  private func loadApp() {
-    let url = URL(string: "http://\(host):\(port)/")!
-    webView.load(URLRequest(url: url))
+  private func isBackendURL(_ url: URL) -> Bool {
+    return url.scheme == BackendTLS.scheme && url.host == host && url.port == port
   }
 
-  private func setupMenu() {
-    let mainMenu = NSMenu()
+  private func openExternally(_ url: URL) {
+    guard let scheme = url.scheme?.lowercased(), ["http", "https", "mailto"].contains(scheme) else { return }
+    NSWorkspace.shared.open(url)
+  }
 
-    // Application menu (first item is always the app menu on macOS)
-    let appMenuItem = NSMenuItem()
-    mainMenu.addItem(appMenuItem)
-    let appMenu = NSMenu()
-    appMenuItem.submenu = appMenu
-    appMenu.addItem(withTitle: "Quit Invest Log", action: #selector(NSApplication.terminate(_:)), keyEquivalent: "q")
+  func userContentController(_ userContentController: WKUserContentController, didReceive message: WKScriptMessage) {
+    if message.name == "shellContext" {
+      guard let url = message.frameInfo.request.url, isBackendURL(url) else { return }
+      (message.webView as? ShellWebView)?.updateMenuContext(from: message.body)
+      return
+    }
+    // Only the bundled loader may drive startup, never pages served by the backend.
+    guard message.name == "loader",
+          message.frameInfo.request.url.map(isLoaderURL) == true,
+          let action = message.body as? String else { return }
+    switch action {
+    case "retry":
+      restartBackend()
+    case "quit":
+      NSApp.terminate(nil)
+    case "openLogs":
+      openLogsFolder(nil)
+    case "copyDiagnostics":
+      copyDiagnostics()
+    default:
+      break
+    }
+  }
 
-    // Edit menu — routes standard edit commands through the responder chain to WKWebView
-    let editMenuItem = NSMenuItem()
-    mainMenu.addItem(editMenuItem)
-    let editMenu = NSMenu(title: "Edit")
-    editMenuItem.submenu = editMenu
-    editMenu.addItem(withTitle: "Undo", action: #selector(UndoManager.undo), keyEquivalent: "z")
-    editMenu.addItem(withTitle: "Redo", action: #selector(UndoManager.redo), keyEquivalent: "Z")
-    editMenu.addItem(NSMenuItem.separator())
-    editMenu.addItem(withTitle: "Cut", action: #selector(NSText.cut(_:)), keyEquivalent: "x")
-    editMenu.addItem(withTitle: "Copy", action: #selector(NSText.copy(_:)), keyEquivalent: "c")
-    editMenu.addItem(withTitle: "Paste", action: #selector(NSText.paste(_:)), keyEquivalent: "v")
-    editMenu.addItem(NSMenuItem.separator())
-    editMenu.addItem(withTitle: "Select All", action: #selector(NSText.selectAll(_:)), keyEquivalent: "a")
+  /// The loader lives in the splash during launch and in the main window during restarts.
+  private var loaderWebView: WKWebView? {
+    if let splash = splashWindow {
+      return splash.contentView as? WKWebView
+    }
+    // webView is still nil while the pipeline starts during launch.
+    return webView?.url?.isFileURL == true ? webView : nil
+  }
 
-    NSApp.mainMenu = mainMenu
+  private func reportStage(_ stage: StartupStage) {
+    ShellLog.info("startup stage", ["stage": stage.label, "elapsed_ms": elapsedStartupMilliseconds])
+    startupStage = stage
+    startupFailure = nil
+    renderLoaderState()
   }
 
-  private func showFatalError(_ message: String) {
-    DispatchQueue.main.async {
-      let alert = NSAlert()
-      alert.messageText = "Invest Log"
-      alert.informativeText = message
-      alert.alertStyle = .critical
-      alert.runModal()
-      NSApp.terminate(nil)
+  private func reportStartupFailure(_ code: StartupError.Code, _ detail: String) {
+    ShellLog.error("startup failed", [
+      "code": code.rawValue,
+      "err": detail,
+      "stage": startupStage.label,
+      "elapsed_ms": elapsedStartupMilliseconds,
+    ])
+    startupFailure = StartupError(code: code, detail: detail, stage: startupStage)
+    backendReady = false
+    statusBar?.setBackendRunning(false)
+    if splashWindow == nil, let webView = webView, webView.url?.isFileURL != true {
+      loadLoadingScreen(in: webView)
+    }
+    renderLoaderState()
+  }
+
+  private var elapsedStartupMilliseconds: Int {
+    return Int(Date().timeIntervalSince(startupStartedAt) * 1000)
+  }
+
+  private func renderLoaderState() {
+    guard let target = loaderWebView else { return }
+    // Structured data goes to the page as arguments, never as markup or script source.
+    if let failure = startupFailure {
+      target.callShellScript("window.showFailure && window.showFailure(failure)", ["failure": failure.payload])
+    } else {
+      target.callShellScript("window.setStage && window.setStage(stage, total, label)", [
+        "stage": startupStage.rawValue,
+        "total": StartupStage.allCases.count,
+        "label": startupStage.label,
+      ])
+      if let cached = DashboardCache.loaderPayload() {
+        target.callShellScript("window.showCachedSummary && window.showCachedSummary(cached)", ["cached": cached])
+      }
+    }
+  }
+
+  func webView(
+    _ webView: WKWebView,
+    didReceive challenge: URLAuthenticationChallenge,
+    completionHandler: @escaping (URLSession.AuthChallengeDisposition, URLCredential?) -> Void
+  ) {
+    BackendTLS.evaluate(challenge, completionHandler: completionHandler)
+  }
+
+  func webView(_ webView: WKWebView, didFailProvisionalNavigation navigation: WKNavigation!, withError error: Error) {
+    // Never leave the user with only a spinner: show whatever the main window has.
+    revealMainWindow()
+  }
+
+  /// Opens an extra window on a SPA route; it shares the backend with the main window.
+  private func openWindow(route: String, title: String) {
+    guard backendReady else {
+      showError("The backend is still starting. Try again in a moment.")
+      return
+    }
+    let extraConfig = WKWebViewConfiguration()
+    extraConfig.userContentController.addScriptMessageHandler(credentialsHandler, contentWorld: .page, name: "credentials")
+    if debugMode {
+      extraConfig.preferences.setValue(true, forKey: "developerExtrasEnabled")
+    }
+    let extraWebView = WKWebView(frame: .zero, configuration: extraConfig)
+    if debugMode, #available(macOS 13.3, *) {
+      extraWebView.isInspectable = true
+    }
+    let extraWindow = NSWindow(
+      contentRect: NSRect(x: 0, y: 0, width: 960, height: 680),
+      styleMask: [.titled, .closable, .miniaturizable, .resizable],
+      backing: .buffered,
+      defer: false
+    )
+    extraWindow.isReleasedWhenClosed = false
+    extraWindow.title = "Invest Log — \(title)" + (debugMode ? " [debug]" : "")
+    extraWindow.identifier = NSUserInterfaceItemIdentifier("route-\(route)")
+    extraWindow.contentView = extraWebView
+    extraWebView.navigationDelegate = self
+    extraWebView.uiDelegate = self
+    restoreZoom(for: extraWindow, webView: extraWebView)
+    extraWindow.delegate = self
+    extraWindow.cascadeTopLeft(from: NSPoint(x: window.frame.minX, y: window.frame.maxY))
+    secondaryWindows.append(extraWindow)
+    appLock.cover(extraWindow)
+
+    extraWebView.load(URLRequest(url: URL(string: "\(BackendTLS.scheme)://\(host):\(port)/#/\(route)")!))
+    extraWindow.makeKeyAndOrderFront(nil)
+  }
+
+  @objc private func openRouteWindow(_ sender: NSMenuItem) {
+    guard let route = sender.representedObject as? String else { return }
+    openWindow(route: route, title: sender.title)
+  }
+
+  func windowWillClose(_ notification: Notification) {
+    guard let closing = notification.object as? NSWindow else { return }
+    if closing === window {
+      mainWindowClosed = true
+    }
+    secondaryWindows.removeAll { $0 === closing }
+  }
+
+  /// The one place shell state reaches the pages: every window showing the app gets the event.
+  private func publishShellEvent(_ type: String, _ detail: [String: Any] = [:]) {
+    var pages: [WKWebView] = [webView]
+    pages += secondaryWindows.compactMap { $0.contentView as? WKWebView }
+    for page in pages where page.url.map(isBackendURL) == true {
+      page.publishShellEvent(type, detail)
+    }
+  }
+
+  /// Secondary windows point at the backend, so they follow it through restarts.
+  private func reloadSecondaryWindows() {
+    for extraWindow in secondaryWindows {
+      guard let extraWebView = extraWindow.contentView as? WKWebView else { continue }
+      // After the local connection switched between http and https, reload() would hit the old scheme.
+      if let url = extraWebView.url, url.scheme != BackendTLS.scheme,
+         var components = URLComponents(url: url, resolvingAgainstBaseURL: false) {
+        components.scheme = BackendTLS.scheme
+        if let rewritten = components.url {
+          extraWebView.load(URLRequest(url: rewritten))
+          continue
+        }
+      }
+      extraWebView.reload()
     }
   }
+
+  private func setupStatusBar() {
+    let controller = StatusBarController()
+    controller.onToggleWindow = { [weak self] in self?.toggleMainWindow() }
+    controller.onRestartBackend = { [weak self] in self?.restartBackend() }
+    controller.onMenuWillOpen = { [weak self] in self?.refreshPortfolioSummary() }
+    controller.onToggleTicker = { [weak self] in self?.toggleTicker() }
+    controller.onSelectLogLevel = { [weak self] level in self?.setLogLevel(level) }
+    controller.setLogLevel(currentLogLevel)
+    statusBar = controller
+
+    summaryTimer = Timer.scheduledTimer(withTimeInterval: 300, repeats: true) { [weak self] _ in
+      self?.refreshPortfolioSummary()
+    }
+    spotlightTimer = Timer.scheduledTimer(withTimeInterval: 3600, repeats: true) { [weak self] _ in
+      self?.refreshSpotlightIndex()
+    }
+  }
+
+  /// Hourly is enough for search; the rebuild replaces the whole index each time.
+  private func refreshSpotlightIndex() {
+    guard backendReady else { return }
+    SpotlightIndex.refresh(baseURL: URL(string: "\(BackendTLS.scheme)://\(host):\(port)/")!)
+  }
+
+  /// Keeps the menu bar totals current even when the main window is closed.
+  private func refreshPortfolioSummary() {
+    guard backendReady, !appLock.isLocked else {
+      statusBar?.updateSummary(nil)
+      ticker?.update(nil)
+      return
+    }
+    PortfolioSummary.fetch(baseURL: URL(string: "\(BackendTLS.scheme)://\(host):\(port)/")!) { [weak self] summary in
+      self?.statusBar?.updateSummary(summary)
+      self?.ticker?.update(summary)
+      if let summary = summary {
+        DashboardCache.save(summary)
+      }
+    }
+  }
+
+  /// Used by the AppleScript commands in ScriptCommands.swift.
+  var isLockedForScripting: Bool {
+    return appLock.isLocked
+  }
+
+  var scriptingBackendURL: URL? {
+    guard backendReady else { return nil }
+    return URL(string: "\(BackendTLS.scheme)://\(host):\(port)/")
+  }
+
+  /// A script added a transaction: open pages and the menu bar totals catch up without a reload.
+  func scriptingDidChangeTransactions() {
+    publishShellEvent("transactions-changed")
+    refreshPortfolioSummary()
+  }
+
+  /// The mini ticker polls more often than the menu bar since it is always on screen.
+  private func toggleTicker() {
+    if let ticker = ticker, ticker.isVisible {
+      ticker.hide()
+      tickerDidClose()
+      return
+    }
+    let controller = ticker ?? TickerWindowController()
+    controller.onClose = { [weak self] in self?.tickerDidClose() }
+    ticker = controller
+    controller.show()
+    statusBar?.setTickerVisible(true)
+    refreshPortfolioSummary()
+    tickerTimer?.invalidate()
+    tickerTimer = Timer.scheduledTimer(withTimeInterval: 60, repeats: true) { [weak self] _ in
+      self?.refreshPortfolioSummary()
+    }
+  }
+
+  private func tickerDidClose() {
+    tickerTimer?.invalidate()
+    tickerTimer = nil
+    statusBar?.setTickerVisible(false)
+  }
+
+  private func showMainWindow() {
+    mainWindowClosed = false
+    window.makeKeyAndOrderFront(nil)
+    NSApp.activate(ignoringOtherApps: true)
+    statusBar?.setWindowVisible(true)
+  }
+
+  private func toggleMainWindow() {
+    if window.isVisible {
+      window.orderOut(nil)
+      lockApp(reason: "hidden")
+    } else {
+      showMainWindow()
+    }
+    statusBar?.setWindowVisible(window.isVisible)
+  }
+
+  /// Shortcut comes from `defaults write com.investlog.app GlobalHotKey "cmd+shift+i"`;
+  /// an empty value disables it.
+  private func registerGlobalHotKey() {
+    let shortcut = UserDefaults.standard.string(forKey: "GlobalHotKey") ?? "cmd+shift+i"
+    guard !shortcut.isEmpty else { return }
+    toggleHotKey = GlobalHotKey(shortcut: shortcut) { [weak self] in
+      self?.toggleMainWindowFocus()
+    }
+    if toggleHotKey == nil {
+      ShellLog.warn("global hot key not registered", ["shortcut": shortcut])
+    }
+
+    let quickAddShortcut = UserDefaults.standard.string(forKey: "QuickAddHotKey") ?? "ctrl+option+t"
+    guard !quickAddShortcut.isEmpty else { return }
+    quickAddHotKey = GlobalHotKey(shortcut: quickAddShortcut) { [weak self] in
+      self?.toggleQuickAdd()
+    }
+    if quickAddHotKey == nil {
+      ShellLog.warn("global hot key not registered", ["shortcut": quickAddShortcut])
+    }
+  }
+
+  @objc private func showQuickAdd(_ sender: Any?) {
+    toggleQuickAdd()
+  }
+
+  /// Floating add-transaction form that closes itself once the SPA saves and
+  /// navigates on to the transactions list.
+  private func toggleQuickAdd() {
+    if appLock.isLocked {
+      showMainWindow()
+      appLock.unlock()
+      return
+    }
+    if let panel = quickAddPanel, panel.isVisible, panel.isKeyWindow {
+      panel.close()
+      return
+    }
+    guard backendReady else {
+      NSSound.beep()
+      return
+    }
+
+    let panel = quickAddPanel ?? makeQuickAddPanel()
+    quickAddPanel = panel
+    if let quickWebView = panel.contentView as? WKWebView {
+      quickWebView.load(URLRequest(url: URL(string: "\(BackendTLS.scheme)://\(host):\(port)/?view=compact#/add")!))
+    }
+    panel.center()
+    panel.makeKeyAndOrderFront(nil)
+    NSApp.activate(ignoringOtherApps: true)
+  }
+
+  private func makeQuickAddPanel() -> NSPanel {
+    let quickWebView = WKWebView(frame: .zero, configuration: WKWebViewConfiguration())
+    quickWebView.navigationDelegate = self
+    quickWebView.uiDelegate = self
+
+    let panel = NSPanel(
+      contentRect: NSRect(x: 0, y: 0, width: 560, height: 720),
+      styleMask: [.titled, .closable, .resizable],
+      backing: .buffered,
+      defer: false
+    )
+    panel.title = "Quick Add Transaction"
+    panel.identifier = NSUserInterfaceItemIdentifier("quick-add")
+    panel.level = .floating
+    panel.isReleasedWhenClosed = false
+    panel.hidesOnDeactivate = false
+    panel.contentView = quickWebView
+    restoreZoom(for: panel, webView: quickWebView)
+
+    quickAddObservation = quickWebView.observe(\.url, options: [.new]) { [weak self] observed, _ in
+      guard let fragment = observed.url?.fragment, fragment.hasPrefix("/transactions") else { return }
+      DispatchQueue.main.async {
+        guard let self = self else { return }
+        self.quickAddPanel?.close()
+        self.publishShellEvent("transactions-changed")
+        self.refreshPortfolioSummary()
+      }
+    }
+    return panel
+  }
+
+  /// Hides the window when it is already frontmost, otherwise brings it forward.
+  private func toggleMainWindowFocus() {
+    if window.isVisible && NSApp.isActive && window.isKeyWindow {
+      window.orderOut(nil)
+      lockApp(reason: "hidden")
+    } else {
+      showMainWindow()
+    }
+    statusBar?.setWindowVisible(window.isVisible)
+  }
+
+  /// Stops the backend off the main thread, then runs the normal startup sequence again.
+  private func restartBackend() {
+    ShellLog.info("restarting backend")
+    backendRestarts += 1
+    timeline.resetBackendPhases()
+    backendReady = false
+    statusBar?.setBackendRunning(false)
+    publishShellEvent("backend", ["state": "restarting"])
+    startupStage = .locating
+    startupFailure = nil
+    if splashWindow == nil {
+      loadLoadingScreen(in: webView)
+    }
+    let oldProcess = backendProcess
+    backendProcess = nil
+    backendOutput = nil
+    DispatchQueue.global(qos: .userInitiated).async {
+      if let oldProcess = oldProcess, oldProcess.isRunning {
+        oldProcess.terminate()
+        oldProcess.waitUntilExit()
+      }
+      DispatchQueue.main.async {
+        self.runStartupPipeline()
+      }
+    }
+  }
+
+  /// locate → spawn → wait for health → navigate; any failure stops at the loader with Retry.
+  private func runStartupPipeline() {
+    startupStartedAt = Date()
+    backendReady = false
+    if startBackend() {
+      reportStage(.waiting)
+      waitForServer(attempt: 0)
+    }
+  }
+
+  private func loadLoadingScreen(in target: WKWebView) {
+    if let url = Bundle.main.url(forResource: "loading", withExtension: "html") {
+      target.loadFileURL(url, allowingReadAccessTo: url.deletingLastPathComponent())
+    } else {
+      let fallback = """
+        <!doctype html><html><head>
+        <meta http-equiv="Content-Security-Policy" content="default-src 'none'; style-src 'unsafe-inline'">
+        <style>
+        :root { color-scheme: light dark; }
+        body { font-family: -apple-system, sans-serif; display: grid; place-items: center; height: 100vh; margin: 0; }
+        </style></head><body><p>Loading…</p></body></html>
+        """
+      target.loadHTMLString(fallback, baseURL: nil)
+    }
+  }
+
+  @discardableResult
+  private func startBackend() -> Bool {
+    reportStage(.locating)
+    guard let resourcePath = Bundle.main.resourcePath else {
+      reportStartupFailure(.resourcesMissing, "Bundle resource path is unavailable.")
+      return false
+    }
+
+    let backendURL = BackendBinary.url(in: URL(fileURLWithPath: resourcePath))
+    let webDirURL = URL(fileURLWithPath: resourcePath).appendingPathComponent("static")
+    guard FileManager.default.isExecutableFile(atPath: backendURL.path) else {
+      reportStartupFailure(.backendMissing, backendURL.path)
+      return false
+    }
+
+    let process = Process()
+    process.executableURL = backendURL
+    process.arguments = [
+      "--host", LANAccess.isEnabled ? "0.0.0.0" : host,
+      "--port", "\(port)",
+      "--web-dir", webDirURL.path
+    ]
+    if debugMode {
+      process.arguments?.append("--debug")
+    }
+    if BackendTLS.isEnabled {
+      process.arguments? += ["--tls-dir", BackendTLS.directory.path]
+    }
+    process.currentDirectoryURL = URL(fileURLWithPath: resourcePath)
+    var env = ProcessInfo.processInfo.environment
+    env["INVEST_LOG_PARENT_WATCH"] = "1"
+    if debugMode {
+      // The env var outranks --debug in the backend, so a saved level must not mask it.
+      env["INVEST_LOG_LOG_LEVEL"] = "debug"
+    } else if UserDefaults.standard.string(forKey: "LogLevel") != nil {
+      env["INVEST_LOG_LOG_LEVEL"] = currentLogLevel.rawValue.lowercased()
+    }
+    if LANAccess.isEnabled {
+      env["INVEST_LOG_LAN_PIN"] = LANAccess.pin()
+    }
+    env.merge(SystemProxy.environment(inherited: env)) { _, proxy in proxy }
+    process.environment = env
+    let output = BackendOutput()
+    output.attach(to: process)
+    process.terminationHandler = { [weak self] terminated in
+      ShellLog.warn("backend exited", [
+        "pid": terminated.processIdentifier,
+        "status": terminated.terminationStatus,
+        "reason": terminated.terminationReason == .uncaughtSignal ? "signal" : "exit",
+      ])
+      DispatchQueue.main.async {
+        guard let self = self, self.backendProcess === terminated else { return }
+        self.statusBar?.setBackendRunning(false)
+        self.publishShellEvent("backend", ["state": "stopped"])
+        if self.debugMode && self.backendReady {
+          self.reportBackendFailure(.backendExited, "Exit code \(terminated.terminationStatus)")
+        }
+      }
+    }
+
+    reportStage(.spawning)
+    do {
+      try process.run()
+      backendProcess = process
+      backendOutput = output
+      backendStartedAt = Date()
+      ShellLog.info("backend spawned", ["pid": process.processIdentifier, "path": backendURL.path])
+      timeline.mark(.backendSpawned)
+      return true
+    } catch {
+      reportStartupFailure(.spawnFailed, error.localizedDescription)
+      return false
+    }
+  }
+
+  private func waitForServer(attempt: Int) {
+    let url = URL(string: "\(BackendTLS.scheme)://\(host):\(port)/api/health")!
+    var request = URLRequest(url: url)
+    request.timeoutInterval = 1.0
+
+    BackendTLS.session.dataTask(with: request) { [weak self] _, response, _ in
+      guard let self = self else { return }
+      if let http = response as? HTTPURLResponse, http.statusCode == 200 {
+        DispatchQueue.main.async {
+          ShellLog.info("backend healthy", ["attempts": attempt + 1, "elapsed_ms": self.elapsedStartupMilliseconds])
+          self.timeline.mark(.backendHealthy)
+          self.reportStage(.navigating)
+          self.backendReady = true
+          self.statusBar?.setBackendRunning(true)
+          self.publishShellEvent("backend", ["state": "running"])
+          self.refreshPortfolioSummary()
+          self.refreshSpotlightIndex()
+          self.fetchStorageInfo { info in
+            if let info = info {
+              let dataDir = URL(fileURLWithPath: info.dataDir, isDirectory: true)
+              self.backendLogsDirectory = dataDir.appendingPathComponent("logs")
+              if !self.appLock.isLocked {
+                DiskEncryptionCheck.warnIfUnencrypted(dataDir: dataDir)
+              }
+            }
+          }
+          self.loadApp()
+          self.reloadSecondaryWindows()
+          let files = self.pendingFiles
+          self.pendingFiles = []
+          files.forEach { self.openDatabaseFile($0) }
+        }
+        return
+      }
+      if attempt < self.maxAttempts {
+        DispatchQueue.main.asyncAfter(deadline: .now() + 0.25) {
+          if let process = self.backendProcess, !process.isRunning {
+            self.reportBackendFailure(.backendExited, "Exit code \(process.terminationStatus)")
+            return
+          }
+          // The server logs a failed bind but keeps running; no point waiting out the timeout.
+          if let cause = self.backendOutput?.classify(), cause.code == .portInUse {
+            self.reportStartupFailure(cause.code, cause.line)
+            return
+          }
+          self.waitForServer(attempt: attempt + 1)
+        }
+      } else {
+        DispatchQueue.main.async {
+          self.reportBackendFailure(.timeout, "No response from \(self.host):\(self.port)/api/health")
+        }
+      }
+    }.resume()
+  }
+
+  /// Prefers a specific cause recognised in the backend's output over the generic code.
+  private func reportBackendFailure(_ fallback: StartupError.Code, _ detail: String) {
+    if let cause = backendOutput?.classify() {
+      reportStartupFailure(cause.code, cause.line)
+    } else {
+      reportStartupFailure(fallback, detail)
+    }
+  }
+
+  private func loadApp() {
+    if appLock.isLocked {
+      // Nothing financial is fetched until the user unlocks; show the lock screen instead.
+      loadDeferredByLock = true
+      revealMainWindow()
+      appLock.unlock()
+      return
+    }
+    let route = pendingRoute.map { "#/\($0)" } ?? restoredRouteFragment() ?? ""
+    pendingRoute = nil
+    let url = URL(string: "\(BackendTLS.scheme)://\(host):\(port)/\(route)")
+      ?? URL(string: "\(BackendTLS.scheme)://\(host):\(port)/")!
+    webView.load(URLRequest(url: url))
+  }
+
+  private func strippingRestoreFlag(_ fragment: String) -> String {
+    let parts = fragment.split(separator: "?", maxSplits: 1)
+    guard parts.count == 2 else { return fragment }
+    let query = parts[1].split(separator: "&").filter { $0 != "restored=1" }
+    return query.isEmpty ? String(parts[0]) : "\(parts[0])?\(query.joined(separator: "&"))"
+  }
+
+  /// On the first load after launch, return to the page that was open last time.
+  /// `restored=1` lets the SPA tell a restored view from a fresh navigation.
+  private func restoredRouteFragment() -> String? {
+    guard !didRestoreRoute else { return nil }
+    didRestoreRoute = true
+    guard let fragment = UserDefaults.standard.string(forKey: "LastRoute"),
+          fragment.count > 1, fragment.hasPrefix("/") else { return nil }
+    let separator = fragment.contains("?") ? "&" : "?"
+    return "#\(fragment)\(separator)restored=1"
+  }
+
+  private func setupMenu() {
+    let mainMenu = NSMenu()
+
+    // Application menu (first item is always the app menu on macOS)
+    let appMenuItem = NSMenuItem()
+    mainMenu.addItem(appMenuItem)
+    let appMenu = NSMenu()
+    appMenuItem.submenu = appMenu
+    appMenu.addItem(withTitle: "About Invest Log", action: #selector(NSApplication.orderFrontStandardAboutPanel(_:)), keyEquivalent: "")
+    appMenu.addItem(NSMenuItem.separator())
+    addItem(to: appMenu, title: "Settings…", action: #selector(openSettings(_:)), key: ",")
+    let lockItem = addItem(to: appMenu, title: "Require Touch ID or Password", action: #selector(toggleAppLock(_:)), key: "")
+    lockItem.state = AppLock.isEnabled ? .on : .off
+    addItem(to: appMenu, title: "Lock Invest Log", action: #selector(lockNow(_:)), key: "l").keyEquivalentModifierMask = [.command, .control]
+    let tlsItem = addItem(to: appMenu, title: "Encrypt Local Connection", action: #selector(toggleBackendTLS(_:)), key: "")
+    tlsItem.state = BackendTLS.isEnabled ? .on : .off
+    backendTLSMenuItem = tlsItem
+    let lanItem = addItem(to: appMenu, title: "Allow Access from Local Network…", action: #selector(toggleLANAccess(_:)), key: "")
+    lanItem.state = LANAccess.isEnabled ? .on : .off
+    lanAccessMenuItem = lanItem
+    addItem(to: appMenu, title: "Show Pairing QR Code…", action: #selector(showLANPairingQRCode(_:)), key: "")
+    let autoLockItem = NSMenuItem(title: "Auto-Lock", action: nil, keyEquivalent: "")
+    let autoLockMenu = NSMenu(title: "Auto-Lock")
+    for minutes in [0, 1, 5, 15, 30, 60] {
+      let title = minutes == 0 ? "Never" : "After \(minutes) Minute" + (minutes == 1 ? "" : "s")
+      let item = addItem(to: autoLockMenu, title: title, action: #selector(selectAutoLock(_:)), key: "")
+      item.tag = minutes
+      item.state = minutes == AppLock.idleMinutes ? .on : .off
+    }
+    autoLockItem.submenu = autoLockMenu
+    appMenu.addItem(autoLockItem)
+    appMenu.addItem(NSMenuItem.separator())
+    appMenu.addItem(withTitle: "Hide Invest Log", action: #selector(NSApplication.hide(_:)), keyEquivalent: "h")
+    let hideOthersItem = appMenu.addItem(withTitle: "Hide Others", action: #selector(NSApplication.hideOtherApplications(_:)), keyEquivalent: "h")
+    hideOthersItem.keyEquivalentModifierMask = [.command, .option]
+    appMenu.addItem(withTitle: "Show All", action: #selector(NSApplication.unhideAllApplications(_:)), keyEquivalent: "")
+    appMenu.addItem(NSMenuItem.separator())
+    appMenu.addItem(withTitle: "Quit Invest Log", action: #selector(NSApplication.terminate(_:)), keyEquivalent: "q")
+
+    // File menu
+    let fileMenuItem = NSMenuItem()
+    mainMenu.addItem(fileMenuItem)
+    let fileMenu = NSMenu(title: "File")
+    fileMenuItem.submenu = fileMenu
+    addItem(to: fileMenu, title: "Add Transaction", action: #selector(openAddTransaction(_:)), key: "n")
+    addItem(to: fileMenu, title: "Quick Add Transaction…", action: #selector(showQuickAdd(_:)), key: "")
+    let newWindowItem = NSMenuItem(title: "New Window", action: nil, keyEquivalent: "")
+    let newWindowMenu = NSMenu(title: "New Window")
+    for (title, route) in [("Overview", "overview"), ("Holdings", "holdings"), ("Transactions", "transactions"), ("Charts", "charts")] {
+      let item = addItem(to: newWindowMenu, title: title, action: #selector(openRouteWindow(_:)), key: "")
+      item.representedObject = route
+    }
+    newWindowItem.submenu = newWindowMenu
+    fileMenu.addItem(newWindowItem)
+    fileMenu.addItem(NSMenuItem.separator())
+    addItem(to: fileMenu, title: "Open Database…", action: #selector(openDatabaseFromMenu(_:)), key: "o")
+    addItem(to: fileMenu, title: "Export Data…", action: #selector(exportData(_:)), key: "e").keyEquivalentModifierMask = [.command, .shift]
+    addItem(to: fileMenu, title: "Show Data Folder", action: #selector(openDataFolder(_:)), key: "")
+    fileMenu.addItem(NSMenuItem.separator())
+    addItem(to: fileMenu, title: "Export as PDF…", action: #selector(exportPDF(_:)), key: "")
+    addItem(to: fileMenu, title: "Export Screenshot…", action: #selector(exportScreenshot(_:)), key: "")
+    addItem(to: fileMenu, title: "Share…", action: #selector(shareCurrentView(_:)), key: "")
+    addItem(to: fileMenu, title: "Print…", action: #selector(printCurrentView(_:)), key: "p")
+    fileMenu.addItem(NSMenuItem.separator())
+    fileMenu.addItem(withTitle: "Close Window", action: #selector(NSWindow.performClose(_:)), keyEquivalent: "w")
+
+    // Edit menu — routes standard edit commands through the responder chain to WKWebView
+    let editMenuItem = NSMenuItem()
+    mainMenu.addItem(editMenuItem)
+    let editMenu = NSMenu(title: "Edit")
+    editMenuItem.submenu = editMenu
+    editMenu.addItem(withTitle: "Undo", action: #selector(UndoManager.undo), keyEquivalent: "z")
+    editMenu.addItem(withTitle: "Redo", action: #selector(UndoManager.redo), keyEquivalent: "Z")
+    editMenu.addItem(NSMenuItem.separator())
+    editMenu.addItem(withTitle: "Cut", action: #selector(NSText.cut(_:)), keyEquivalent: "x")
+    editMenu.addItem(withTitle: "Copy", action: #selector(NSText.copy(_:)), keyEquivalent: "c")
+    editMenu.addItem(withTitle: "Paste", action: #selector(NSText.paste(_:)), keyEquivalent: "v")
+    editMenu.addItem(NSMenuItem.separator())
+    editMenu.addItem(withTitle: "Select All", action: #selector(NSText.selectAll(_:)), keyEquivalent: "a")
+
+    // View menu
+    let viewMenuItem = NSMenuItem()
+    mainMenu.addItem(viewMenuItem)
+    let viewMenu = NSMenu(title: "View")
+    viewMenuItem.submenu = viewMenu
+    addItem(to: viewMenu, title: "Command Palette…", action: #selector(toggleCommandPalette(_:)), key: "k")
+    addItem(to: viewMenu, title: "Reload", action: #selector(reloadPage(_:)), key: "r")
+    viewMenu.addItem(NSMenuItem.separator())
+    addItem(to: viewMenu, title: "Actual Size", action: #selector(resetZoom(_:)), key: "0")
+    addItem(to: viewMenu, title: "Zoom In", action: #selector(zoomIn(_:)), key: "=")
+    addItem(to: viewMenu, title: "Zoom Out", action: #selector(zoomOut(_:)), key: "-")
+    viewMenu.addItem(NSMenuItem.separator())
+    let titleBarItem = NSMenuItem(title: "Title Bar", action: nil, keyEquivalent: "")
+    let titleBarMenu = NSMenu(title: "Title Bar")
+    for (title, style) in [("Standard", "standard"), ("Overlay", "overlay")] {
+      let item = addItem(to: titleBarMenu, title: title, action: #selector(selectTitleBarStyle(_:)), key: "")
+      item.representedObject = style
+      item.state = (style == "overlay") == titleBarOverlay ? .on : .off
+    }
+    titleBarItem.submenu = titleBarMenu
+    viewMenu.addItem(titleBarItem)
+    let shieldItem = addItem(to: viewMenu, title: "Hide Contents When Inactive", action: #selector(togglePrivacyShield(_:)), key: "")
+    shieldItem.state = PrivacyShield.isEnabled ? .on : .off
+    let vibrancyItem = addItem(to: viewMenu, title: "Translucent Background", action: #selector(toggleVibrancy(_:)), key: "")
+    vibrancyItem.state = vibrancyEnabled ? .on : .off
+    viewMenu.addItem(NSMenuItem.separator())
+    let presentationItem = addItem(to: viewMenu, title: "Presentation Mode", action: #selector(togglePresentationMode(_:)), key: "p")
+    presentationItem.keyEquivalentModifierMask = [.command, .shift]
+    let fullScreenItem = viewMenu.addItem(withTitle: "Enter Full Screen", action: #selector(NSWindow.toggleFullScreen(_:)), keyEquivalent: "f")
+    fullScreenItem.keyEquivalentModifierMask = [.command, .control]
+
+    // Window menu
+    let windowMenuItem = NSMenuItem()
+    mainMenu.addItem(windowMenuItem)
+    let windowMenu = NSMenu(title: "Window")
+    windowMenuItem.submenu = windowMenu
+    windowMenu.addItem(withTitle: "Minimize", action: #selector(NSWindow.performMiniaturize(_:)), keyEquivalent: "m")
+    windowMenu.addItem(withTitle: "Zoom", action: #selector(NSWindow.performZoom(_:)), keyEquivalent: "")
+    NSApp.windowsMenu = windowMenu
+
+    // Help menu
+    let helpMenuItem = NSMenuItem()
+    mainMenu.addItem(helpMenuItem)
+    let helpMenu = NSMenu(title: "Help")
+    helpMenuItem.submenu = helpMenu
+    addItem(to: helpMenu, title: "Show Logs", action: #selector(showLogViewer(_:)), key: "")
+    addItem(to: helpMenu, title: "Show Logs Folder", action: #selector(openLogsFolder(_:)), key: "")
+    helpMenu.addItem(NSMenuItem.separator())
+    addItem(to: helpMenu, title: "App Health", action: #selector(showHealthWindow(_:)), key: "")
+    addItem(to: helpMenu, title: "Startup Timings", action: #selector(showStartupTimings(_:)), key: "")
+    addItem(to: helpMenu, title: "Run Self-Test", action: #selector(runSelfTest(_:)), key: "")
+    addItem(to: helpMenu, title: "Copy Diagnostics", action: #selector(copyDiagnosticsFromMenu(_:)), key: "")
+    let logLevelItem = NSMenuItem(title: "Log Level", action: nil, keyEquivalent: "")
+    for level in [ShellLog.Level.debug, .info, .warn, .error] {
+      let item = addItem(to: logLevelMenu, title: level.rawValue.capitalized, action: #selector(selectLogLevel(_:)), key: "")
+      item.representedObject = level.rawValue
+      item.state = level == currentLogLevel ? .on : .off
+    }
+    logLevelItem.submenu = logLevelMenu
+    helpMenu.addItem(logLevelItem)
+    let metricsItem = addItem(to: helpMenu, title: "Serve Metrics on 127.0.0.1:\(metricsPort)", action: #selector(toggleMetrics(_:)), key: "")
+    metricsItem.state = UserDefaults.standard.bool(forKey: "MetricsEnabled") ? .on : .off
+    NSApp.helpMenu = helpMenu
+
+    NSApp.mainMenu = mainMenu
+  }
+
+  @discardableResult
+  private func addItem(to menu: NSMenu, title: String, action: Selector, key: String) -> NSMenuItem {
+    let item = menu.addItem(withTitle: title, action: action, keyEquivalent: key)
+    item.target = self
+    return item
+  }
+
+  /// Switches the SPA to a hash route, loading the app first if the loader is still showing.
+  /// Routes must already be sanitized (see DeepLink) since they become part of the page URL.
+  private func navigate(to route: String) {
+    let appURL = URL(string: "\(BackendTLS.scheme)://\(host):\(port)/#/\(route)")!
+    if loadDeferredByLock {
+      pendingRoute = route
+    } else if webView.url?.host == host {
+      webView.callShellScript("window.location.hash = hash", ["hash": "#/\(route)"])
+    } else {
+      webView.load(URLRequest(url: appURL))
+    }
+    showMainWindow()
+  }
+
+  /// Native right-click menu; row-specific items appear only when the page reported a holding.
+  private func buildContextMenu(for view: ShellWebView, into menu: NSMenu) {
+    let context = view.menuContext
+    menu.autoenablesItems = false
+    let copyItem = menu.addItem(withTitle: "Copy", action: #selector(NSText.copy(_:)), keyEquivalent: "")
+    copyItem.isEnabled = context.hasSelection
+
+    if let symbol = context.symbol {
+      menu.addItem(NSMenuItem.separator())
+      let addItem = menu.addItem(withTitle: "Add Transaction for \(symbol)", action: #selector(addTransactionFromContext(_:)), keyEquivalent: "")
+      addItem.target = self
+      addItem.representedObject = context
+      if context.currency != nil {
+        let analysisItem = menu.addItem(withTitle: "Analyze \(symbol)", action: #selector(analyzeSymbolFromContext(_:)), keyEquivalent: "")
+        analysisItem.target = self
+        analysisItem.representedObject = context
+      }
+    }
+
+    menu.addItem(NSMenuItem.separator())
+    let backItem = menu.addItem(withTitle: "Back", action: #selector(WKWebView.goBack(_:)), keyEquivalent: "")
+    backItem.target = view
+    backItem.isEnabled = view.canGoBack
+    let reloadItem = menu.addItem(withTitle: "Reload", action: #selector(WKWebView.reload(_:)), keyEquivalent: "")
+    reloadItem.target = view
+    menu.addItem(NSMenuItem.separator())
+    let logsItem = menu.addItem(withTitle: "Show Logs", action: #selector(showLogViewer(_:)), keyEquivalent: "")
+    logsItem.target = self
+  }
+
+  @objc private func addTransactionFromContext(_ sender: NSMenuItem) {
+    guard let context = sender.representedObject as? ShellWebView.MenuContext else { return }
+    navigateFromContext("add", context: context)
+  }
+
+  @objc private func analyzeSymbolFromContext(_ sender: NSMenuItem) {
+    guard let context = sender.representedObject as? ShellWebView.MenuContext else { return }
+    navigateFromContext("symbol-analysis", context: context)
+  }
+
+  /// Goes through DeepLink so values taken from the page are re-encoded before reaching JS.
+  private func navigateFromContext(_ route: String, context: ShellWebView.MenuContext) {
+    var components = URLComponents()
+    components.scheme = DeepLink.scheme
+    components.host = route
+    components.queryItems = [
+      ("symbol", context.symbol),
+      ("currency", context.currency),
+      ("account", context.account),
+    ].compactMap { name, value in value.map { URLQueryItem(name: name, value: $0) } }
+    guard let url = components.url, let target = DeepLink.route(from: url) else { return }
+    navigate(to: target)
+  }
+
+  /// Full screen with the menu bar item, mini ticker and (optionally) amounts hidden,
+  /// for reviewing the portfolio on a TV. Leaving full screen ends it too.
+  @objc private func togglePresentationMode(_ sender: Any?) {
+    if presentationMode {
+      if window.styleMask.contains(.fullScreen) {
+        window.toggleFullScreen(nil)
+      } else {
+        endPresentationMode()
+      }
+      return
+    }
+
+    presentationMode = true
+    showMainWindow()
+    statusBar?.setHidden(true)
+    ticker?.hide()
+    NSApp.presentationOptions = [.autoHideMenuBar, .autoHideDock]
+    // PresentationPrivacy defaults to on; it reuses the SPA's own privacy masking.
+    if UserDefaults.standard.object(forKey: "PresentationPrivacy") as? Bool ?? true {
+      webView.callShellScript("document.body.classList.add('privacy')")
+    }
+    if !window.styleMask.contains(.fullScreen) {
+      window.toggleFullScreen(nil)
+    }
+  }
+
+  func windowDidMove(_ notification: Notification) {
+    if (notification.object as? NSWindow) === window {
+      WindowLayout.save(window)
+    }
+  }
+
+  func windowDidEndLiveResize(_ notification: Notification) {
+    if (notification.object as? NSWindow) === window {
+      WindowLayout.save(window)
+    }
+  }
+
+  /// Zoom is remembered per display, so a window dragged to another screen picks up that screen's factor.
+  func windowDidChangeScreen(_ notification: Notification) {
+    guard let target = notification.object as? NSWindow, let targetWebView = hostedWebView(in: target) else { return }
+    restoreZoom(for: target, webView: targetWebView)
+  }
+
+  /// Docking or undocking moves the window to another display; put it where it last was there.
+  @objc private func screenParametersDidChange(_ notification: Notification) {
+    guard !window.styleMask.contains(.fullScreen) else { return }
+    if WindowLayout.restore(window, on: window.screen) {
+      restoreZoom(for: window, webView: webView)
+    }
+  }
+
+  func windowDidExitFullScreen(_ notification: Notification) {
+    if presentationMode, (notification.object as? NSWindow) === window {
+      endPresentationMode()
+    }
+  }
+
+  private func endPresentationMode() {
+    presentationMode = false
+    NSApp.presentationOptions = []
+    statusBar?.setHidden(false)
+    webView.callShellScript("document.body.classList.toggle('privacy', localStorage.getItem('privacyMode') === '1')")
+  }
+
+  @objc private func toggleCommandPalette(_ sender: Any?) {
+    commandPalette.toggle(over: NSApp.keyWindow ?? window)
+  }
+
+  /// Page shortcuts plus every actionable item in the native menus, so new menu
+  /// items show up in the palette without being registered twice.
+  private func paletteCommands() -> [CommandPalette.Command] {
+    let pages = [
+      ("Overview", "overview"), ("Holdings", "holdings"), ("AI Analysis", "ai-analysis"),
+      ("Charts", "charts"), ("Transactions", "transactions"), ("Transfer", "transfer"), ("Settings", "settings"),
+    ]
+    var commands = pages.map { page in
+      CommandPalette.Command(title: "Go to \(page.0)") { [weak self] in self?.navigate(to: page.1) }
+    }
+    if let mainMenu = NSApp.mainMenu {
+      collectMenuCommands(from: mainMenu, prefix: nil, into: &commands)
+    }
+    return commands
+  }
+
+  private func collectMenuCommands(from menu: NSMenu, prefix: String?, into commands: inout [CommandPalette.Command]) {
+    for item in menu.items where !item.isSeparatorItem && !item.isHidden {
+      if let submenu = item.submenu {
+        let title = submenu.title.isEmpty ? item.title : submenu.title
+        collectMenuCommands(from: submenu, prefix: title.isEmpty ? prefix : title, into: &commands)
+        continue
+      }
+      guard let action = item.action, action != #selector(toggleCommandPalette(_:)) else { continue }
+      let title = prefix.map { "\($0) › \(item.title)" } ?? item.title
+      commands.append(CommandPalette.Command(title: title) {
+        NSApp.sendAction(action, to: item.target, from: item)
+      })
+    }
+  }
+
+  /// Screen-sharing apps starting or quitting change whether menu bar amounts are masked.
+  private func setupPrivacyShield() {
+    privacyShield.onMaskChange = { [weak self] in
+      guard let self = self else { return }
+      PortfolioSummary.hidesAmounts = self.privacyShield.masksAmounts
+      self.refreshPortfolioSummary()
+    }
+    let workspace = NSWorkspace.shared.notificationCenter
+    for name in [NSWorkspace.didLaunchApplicationNotification, NSWorkspace.didTerminateApplicationNotification] {
+      workspace.addObserver(self, selector: #selector(runningApplicationsDidChange(_:)), name: name, object: nil)
+    }
+    updatePrivacyShield()
+  }
+
+  private func updatePrivacyShield() {
+    privacyShield.update(windows: [window] + secondaryWindows, appActive: NSApp.isActive)
+  }
+
+  @objc private func runningApplicationsDidChange(_ notification: Notification) {
+    updatePrivacyShield()
+  }
+
+  @objc private func togglePrivacyShield(_ sender: NSMenuItem) {
+    PrivacyShield.isEnabled.toggle()
+    sender.state = PrivacyShield.isEnabled ? .on : .off
+    if PrivacyShield.isEnabled {
+      DashboardCache.clear()
+    }
+    updatePrivacyShield()
+  }
+
+  /// Locks at launch and whenever the app or screen goes away: hidden, window sent to the
+  /// menu bar, display asleep, or the user session switched out.
+  private func setupAppLock() {
+    appLock.onUnlock = { [weak self] in
+      guard let self = self else { return }
+      self.refreshPortfolioSummary()
+      if self.loadDeferredByLock {
+        self.loadDeferredByLock = false
+        self.loadApp()
+      }
+    }
+    lockApp(reason: "launch")
+    appLock.startIdleMonitor { [weak self] in self?.lockApp(reason: "idle") }
+    NotificationCenter.default.addObserver(
+      self, selector: #selector(lockOnNotification(_:)), name: NSApplication.didHideNotification, object: nil
+    )
+    NotificationCenter.default.addObserver(
+      self, selector: #selector(lockOnNotification(_:)), name: NSWindow.didMiniaturizeNotification, object: window
+    )
+    let workspace = NSWorkspace.shared.notificationCenter
+    for name in [NSWorkspace.screensDidSleepNotification, NSWorkspace.sessionDidResignActiveNotification] {
+      workspace.addObserver(self, selector: #selector(lockOnNotification(_:)), name: name, object: nil)
+    }
+  }
+
+  private func lockApp(reason: String) {
+    guard AppLock.isEnabled, !appLock.isLocked else { return }
+    appLock.lock([window] + secondaryWindows, reason: reason)
+    quickAddPanel?.close()
+    if let ticker = ticker, ticker.isVisible {
+      ticker.hide()
+      tickerDidClose()
+    }
+    statusBar?.updateSummary(nil)
+  }
+
+  @objc private func lockOnNotification(_ notification: Notification) {
+    lockApp(reason: notification.name.rawValue)
+  }
+
+  @objc private func lockNow(_ sender: Any?) {
+    guard AppLock.isEnabled else {
+      NSSound.beep()
+      return
+    }
+    lockApp(reason: "manual")
+  }
+
+  /// Switching schemes needs a fresh backend; the app reloads once it answers again.
+  @objc private func toggleBackendTLS(_ sender: NSMenuItem) {
+    BackendTLS.isEnabled.toggle()
+    sender.state = BackendTLS.isEnabled ? .on : .off
+    ShellLog.info("backend tls setting changed", ["enabled": BackendTLS.isEnabled])
+    if !BackendTLS.isEnabled && LANAccess.isEnabled {
+      // Never serve the network in plain HTTP.
+      disableLANAccess()
+    }
+    restartBackend()
+  }
+
+  /// Enabling asks first and turns on TLS; while on, the item shows the address and PIN.
+  @objc private func toggleLANAccess(_ sender: NSMenuItem) {
+    if LANAccess.isEnabled {
+      if showLANPairingInfo(offerTurnOff: true) {
+        disableLANAccess()
+        restartBackend()
+      }
+      return
+    }
+
+    let alert = NSAlert()
+    alert.messageText = "Allow access from your local network?"
+    alert.informativeText = """
+      Other devices on this network, such as your phone, can open Invest Log in a browser \
+      after entering a PIN. The connection is encrypted with a certificate your browser will \
+      ask you to trust once. Only turn this on at home or on other networks you trust.
+      """
+    alert.addButton(withTitle: "Allow")
+    alert.addButton(withTitle: "Cancel")
+    guard alert.runModal() == .alertFirstButtonReturn else { return }
+
+    LANAccess.isEnabled = true
+    if !BackendTLS.isEnabled {
+      BackendTLS.isEnabled = true
+      backendTLSMenuItem?.state = .on
+    }
+    sender.state = .on
+    ShellLog.info("lan access setting changed", ["enabled": true])
+    restartBackend()
+    _ = showLANPairingInfo(offerTurnOff: false)
+  }
+
+  private func disableLANAccess() {
+    LANAccess.isEnabled = false
+    LANAccess.resetPIN()
+    lanAccessMenuItem?.state = .off
+    ShellLog.info("lan access setting changed", ["enabled": false])
+  }
+
+  /// Returns true if the user chose to turn access off.
+  private func showLANPairingInfo(offerTurnOff: Bool) -> Bool {
+    let addresses = LANAccess.localAddresses().map { "https://\($0):\(port)" }
+    let alert = NSAlert()
+    alert.messageText = "Open Invest Log on another device"
+    alert.informativeText = """
+      Address: \(addresses.isEmpty ? "not connected to a network" : addresses.joined(separator: "\n"))
+      PIN: \(LANAccess.pin())
+
+      Enter the PIN when the page asks for it. Paired browsers need the PIN again after \
+      Invest Log restarts.
+      """
+    alert.addButton(withTitle: "OK")
+    if offerTurnOff {
+      alert.addButton(withTitle: "Turn Off")
+    }
+    return alert.runModal() == .alertSecondButtonReturn
+  }
+
+  /// The code carries a single-use link that pairs a phone as a read-only session, valid for
+  /// a few minutes, so nobody has to type the address or PIN.
+  @objc private func showLANPairingQRCode(_ sender: Any?) {
+    guard LANAccess.isEnabled else {
+      showError("Turn on Invest Log > Allow Access from Local Network first.")
+      return
+    }
+    guard backendReady, let address = LANAccess.localAddresses().first else {
+      showError("Invest Log is not reachable on the local network right now.")
+      return
+    }
+    var request = URLRequest(url: URL(string: "\(BackendTLS.scheme)://\(host):\(port)/lan/token")!)
+    request.httpMethod = "POST"
+    request.timeoutInterval = 5.0
+    BackendTLS.session.dataTask(with: request) { [weak self] data, response, _ in
+      var link: URL?
+      if (response as? HTTPURLResponse)?.statusCode == 200, let data = data,
+         let body = try? JSONSerialization.jsonObject(with: data) as? [String: Any],
+         let path = body["path"] as? String {
+        link = URL(string: "https://\(address):\(self?.port ?? 8000)\(path)")
+      }
+      DispatchQueue.main.async {
+        guard let self = self else { return }
+        guard let link = link, let image = LANAccess.qrCode(for: link) else {
+          self.showError("Unable to create a pairing code.")
+          return
+        }
+        let alert = NSAlert()
+        alert.messageText = "Scan to open Invest Log"
+        alert.informativeText = """
+          Scan with the phone's camera on the same network. The code works once, within \
+          5 minutes, and gives read-only access; use the PIN for full access.
+          """
+        let imageView = NSImageView(frame: NSRect(origin: .zero, size: image.size))
+        imageView.image = image
+        alert.accessoryView = imageView
+        alert.addButton(withTitle: "Done")
+        alert.runModal()
+      }
+    }.resume()
+  }
+
+  /// Only takes effect while the lock is turned on.
+  @objc private func selectAutoLock(_ sender: NSMenuItem) {
+    AppLock.idleMinutes = sender.tag
+    sender.menu?.items.forEach { $0.state = $0 === sender ? .on : .off }
+    appLock.noteActivity()
+  }
+
+  /// Both directions need a successful authentication, so a passer-by can't switch it off.
+  @objc private func toggleAppLock(_ sender: NSMenuItem) {
+    guard AppLock.isAvailable else {
+      showError("Touch ID or a login password is required to lock Invest Log.")
+      return
+    }
+    let enable = !AppLock.isEnabled
+    AppLock.authenticate(reason: enable ? "turn on the Invest Log lock" : "turn off the Invest Log lock") { success in
+      guard success else { return }
+      AppLock.isEnabled = enable
+      // Indexed transactions and cached totals would be visible without unlocking.
+      if enable {
+        SpotlightIndex.clear()
+        DashboardCache.clear()
+      } else {
+        self.refreshSpotlightIndex()
+      }
+      sender.state = enable ? .on : .off
+      ShellLog.info("app lock setting changed", ["enabled": enable])
+    }
+  }
+
+  @objc private func openSettings(_ sender: Any?) {
+    navigate(to: "settings")
+  }
+
+  @objc private func openAddTransaction(_ sender: Any?) {
+    navigate(to: "add")
+  }
+
+  @objc private func reloadPage(_ sender: Any?) {
+    webView.reload()
+  }
+
+  @objc private func zoomIn(_ sender: Any?) {
+    adjustZoom { min($0 + 0.1, 3.0) }
+  }
+
+  @objc private func zoomOut(_ sender: Any?) {
+    adjustZoom { max($0 - 0.1, 0.5) }
+  }
+
+  @objc private func resetZoom(_ sender: Any?) {
+    adjustZoom { _ in 1.0 }
+  }
+
+  /// Zooms the frontmost window and remembers the factor under that window's identifier.
+  private func adjustZoom(_ transform: (CGFloat) -> CGFloat) {
+    let target = NSApp.keyWindow ?? window!
+    guard let targetWebView = hostedWebView(in: target),
+          let identifier = target.identifier?.rawValue else { return }
+    let zoom = (transform(targetWebView.pageZoom) * 10).rounded() / 10
+    targetWebView.pageZoom = zoom
+    UserDefaults.standard.set(Double(zoom), forKey: "PageZoom.\(identifier)")
+    if let screenKey = WindowLayout.key(for: target.screen) {
+      UserDefaults.standard.set(Double(zoom), forKey: "PageZoom.\(identifier).\(screenKey)")
+    }
+  }
+
+  /// Prefers the factor saved for the window's current display, then the window-wide one.
+  private func restoreZoom(for target: NSWindow, webView targetWebView: WKWebView) {
+    guard let identifier = target.identifier?.rawValue else { return }
+    let perScreen = WindowLayout.key(for: target.screen)
+      .map { UserDefaults.standard.double(forKey: "PageZoom.\(identifier).\($0)") } ?? 0
+    let saved = perScreen > 0 ? perScreen : UserDefaults.standard.double(forKey: "PageZoom.\(identifier)")
+    if saved > 0 {
+      targetWebView.pageZoom = CGFloat(saved)
+    }
+  }
+
+  /// The window the user is looking at and its page: a secondary window when one is key,
+  /// otherwise the main window.
+  private func frontmostPage() -> (window: NSWindow, webView: WKWebView) {
+    if let key = NSApp.keyWindow, let page = hostedWebView(in: key) {
+      return (key, page)
+    }
+    return (window, webView)
+  }
+
+  /// Runs the page through the print pipeline; passing a URL saves a PDF without any panel.
+  private func makePrintOperation(of page: WKWebView, savingTo pdfURL: URL? = nil) -> NSPrintOperation {
+    let printInfo = NSPrintInfo.shared.copy() as! NSPrintInfo
+    printInfo.horizontalPagination = .fit
+    printInfo.verticalPagination = .automatic
+    printInfo.isVerticallyCentered = false
+    if let pdfURL = pdfURL {
+      printInfo.jobDisposition = .save
+      printInfo.dictionary()[NSPrintInfo.AttributeKey.jobSavingURL] = pdfURL
+    }
+
+    let operation = page.printOperation(with: printInfo)
+    operation.showsPrintPanel = pdfURL == nil
+    operation.showsProgressPanel = true
+    // WKWebView's print view has a zero frame until it is given one explicitly.
+    operation.view?.frame = page.bounds
+    return operation
+  }
+
+  /// The page stays loaded under the lock screen, so File-menu actions that print, save or
+  /// share it ask for the unlock first instead, as Quick Add does. True when locked.
+  private func requestUnlockIfLocked() -> Bool {
+    guard appLock.isLocked else { return false }
+    showMainWindow()
+    appLock.unlock()
+    return true
+  }
+
+  @objc private func printCurrentView(_ sender: Any?) {
+    guard !requestUnlockIfLocked() else { return }
+    let front = frontmostPage()
+    makePrintOperation(of: front.webView).runModal(for: front.window, delegate: nil, didRun: nil, contextInfo: nil)
+  }
+
+  @objc private func exportPDF(_ sender: Any?) {
+    guard !requestUnlockIfLocked() else { return }
+    let front = frontmostPage()
+    let panel = NSSavePanel()
+    panel.allowedContentTypes = [.pdf]
+    panel.nameFieldStringValue = "Invest Log \(exportDateStamp()).pdf"
+    panel.beginSheetModal(for: front.window) { [weak self] response in
+      guard let self = self, response == .OK, let url = panel.url else { return }
+      // Let the save sheet finish detaching before the progress sheet attaches.
+      DispatchQueue.main.async {
+        self.makePrintOperation(of: front.webView, savingTo: url)
+          .runModal(for: front.window, delegate: nil, didRun: nil, contextInfo: nil)
+      }
+    }
+  }
+
+  /// Renders the current page to a temporary PDF and offers it to the system share sheet
+  /// (AirDrop, Mail, Messages, …).
+  @objc private func shareCurrentView(_ sender: Any?) {
+    guard !requestUnlockIfLocked() else { return }
+    let directory = FileManager.default.temporaryDirectory.appendingPathComponent("InvestLogShare", isDirectory: true)
+    try? FileManager.default.createDirectory(at: directory, withIntermediateDirectories: true)
+    let pdfURL = directory.appendingPathComponent("Invest Log \(exportDateStamp()).pdf")
+    try? FileManager.default.removeItem(at: pdfURL)
+
+    let front = frontmostPage()
+    pendingShare = (pdfURL, front.webView)
+    // Like exportPDF, run window-modal: WKWebView lays pages out asynchronously, and a
+    // blocking run() on the main thread can finish before it has, leaving a blank PDF.
+    makePrintOperation(of: front.webView, savingTo: pdfURL).runModal(
+      for: front.window,
+      delegate: self,
+      didRun: #selector(sharePrintOperationDidRun(_:success:contextInfo:)),
+      contextInfo: nil
+    )
+  }
+
+  @objc private func sharePrintOperationDidRun(
+    _ operation: NSPrintOperation,
+    success: Bool,
+    contextInfo: UnsafeMutableRawPointer?
+  ) {
+    guard let share = pendingShare else { return }
+    pendingShare = nil
+    guard success, FileManager.default.fileExists(atPath: share.pdfURL.path) else {
+      showError("Unable to prepare the page for sharing.")
+      return
+    }
+
+    let page = share.page
+    let picker = NSSharingServicePicker(items: [share.pdfURL])
+    let anchor = NSRect(x: page.bounds.maxX - 40, y: page.bounds.maxY - 8, width: 1, height: 1)
+    picker.show(relativeTo: anchor, of: page, preferredEdge: .minY)
+  }
+
+  /// Saves the visible page (without window chrome) as a PNG.
+  @objc private func exportScreenshot(_ sender: Any?) {
+    guard !requestUnlockIfLocked() else { return }
+    let front = frontmostPage()
+    let panel = NSSavePanel()
+    panel.allowedContentTypes = [.png]
+    panel.nameFieldStringValue = "Invest Log \(exportDateStamp()).png"
+    panel.beginSheetModal(for: front.window) { [weak self] response in
+      guard let self = self, response == .OK, let url = panel.url else { return }
+      self.captureView(front.webView, to: url)
+    }
+  }
+
+  private func captureView(_ targetWebView: WKWebView, to url: URL) {
+    targetWebView.takeSnapshot(with: WKSnapshotConfiguration()) { [weak self] image, error in
+      guard let image = image,
+            let tiff = image.tiffRepresentation,
+            let bitmap = NSBitmapImageRep(data: tiff),
+            let png = bitmap.representation(using: .png, properties: [:]) else {
+        self?.showError("Unable to capture the page. \(error?.localizedDescription ?? "")")
+        return
+      }
+      do {
+        try png.write(to: url)
+      } catch {
+        self?.showError("Unable to save the screenshot. \(error.localizedDescription)")
+      }
+    }
+  }
+
+  private func exportDateStamp() -> String {
+    let formatter = DateFormatter()
+    formatter.dateFormat = "yyyy-MM-dd"
+    return formatter.string(from: Date())
+  }
+
+  /// Falls back to the shell's own logs when the backend can't say where its data directory is.
+  @objc private func openLogsFolder(_ sender: Any?) {
+    fetchStorageInfo { info in
+      let logsURL = info.map { URL(fileURLWithPath: $0.dataDir, isDirectory: true).appendingPathComponent("logs") }
+      NSWorkspace.shared.open(logsURL ?? ShellLog.directory)
+    }
+  }
+
+  @objc private func showHealthWindow(_ sender: Any?) {
+    healthWindow.show()
+  }
+
+  private func healthState() -> HealthWindowController.ShellState {
+    let running = backendProcess?.isRunning == true
+    return HealthWindowController.ShellState(
+      version: Bundle.main.object(forInfoDictionaryKey: "CFBundleShortVersionString") as? String ?? "unknown",
+      backendRunning: running,
+      backendPID: running ? backendProcess?.processIdentifier : nil,
+      backendStartedAt: running ? backendStartedAt : nil,
+      baseURL: URL(string: "\(BackendTLS.scheme)://\(host):\(port)/")!
+    )
+  }
+
+  /// `defaults write … MetricsPort <n>` moves the endpoint off the usual exporter port.
+  private var metricsPort: UInt16 {
+    let stored = UserDefaults.standard.integer(forKey: "MetricsPort")
+    return (1...65535).contains(stored) ? UInt16(stored) : MetricsServer.defaultPort
+  }
+
+  private func metricsSnapshot() -> MetricsServer.Snapshot {
+    let state = healthState()
+    return MetricsServer.Snapshot(
+      version: state.version,
+      backendRunning: state.backendRunning,
+      backendStartedAt: state.backendStartedAt,
+      backendRestarts: backendRestarts,
+      healthURL: state.baseURL.appendingPathComponent("api/health")
+    )
+  }
+
+  @objc private func toggleMetrics(_ sender: NSMenuItem) {
+    let enabled = !metricsServer.isRunning
+    UserDefaults.standard.set(enabled, forKey: "MetricsEnabled")
+    sender.state = enabled ? .on : .off
+    if enabled {
+      metricsServer.start(port: metricsPort)
+    } else {
+      metricsServer.stop()
+    }
+  }
+
+  @objc private func showStartupTimings(_ sender: Any?) {
+    let alert = NSAlert()
+    alert.messageText = "Startup Timings"
+    alert.informativeText = timeline.summary
+    alert.addButton(withTitle: "OK")
+    alert.addButton(withTitle: "Copy")
+    if alert.runModal() == .alertSecondButtonReturn {
+      NSPasteboard.general.clearContents()
+      NSPasteboard.general.setString(timeline.summary, forType: .string)
+    }
+  }
+
+  private var currentLogLevel: ShellLog.Level {
+    return UserDefaults.standard.string(forKey: "LogLevel").flatMap(ShellLog.Level.init(rawValue:)) ?? .info
+  }
+
+  @objc private func selectLogLevel(_ sender: NSMenuItem) {
+    guard let raw = sender.representedObject as? String, let level = ShellLog.Level(rawValue: raw) else { return }
+    setLogLevel(level)
+  }
+
+  /// Applies to the shell immediately and to the running backend via its API; the saved
+  /// value is also handed to every backend started later.
+  private func setLogLevel(_ level: ShellLog.Level) {
+    UserDefaults.standard.set(level.rawValue, forKey: "LogLevel")
+    ShellLog.minimumLevel = level
+    ShellLog.info("log level changed", ["level": level.rawValue])
+    logLevelMenu.items.forEach { $0.state = ($0.representedObject as? String) == level.rawValue ? .on : .off }
+    statusBar?.setLogLevel(level)
+
+    guard backendReady else { return }
+    var request = URLRequest(url: URL(string: "\(BackendTLS.scheme)://\(host):\(port)/api/log-level")!)
+    request.httpMethod = "PUT"
+    request.setValue("application/json", forHTTPHeaderField: "Content-Type")
+    request.httpBody = try? JSONSerialization.data(withJSONObject: ["level": level.rawValue.lowercased()])
+    request.timeoutInterval = 2.0
+    BackendTLS.session.dataTask(with: request) { _, response, _ in
+      if (response as? HTTPURLResponse)?.statusCode != 200 {
+        ShellLog.warn("backend rejected log level", ["level": level.rawValue])
+      }
+    }.resume()
+  }
+
+  @objc private func copyDiagnosticsFromMenu(_ sender: Any?) {
+    copyDiagnostics()
+  }
+
+  /// Builds the diagnostics zip, puts a short summary and its path on the clipboard, and reveals it.
+  private func copyDiagnostics() {
+    let summary = diagnosticsSummary()
+    let logsDirectory = backendLogsDirectory
+    let operation = operations.begin("Collecting diagnostics")
+    DispatchQueue.global(qos: .userInitiated).async {
+      let bundleURL = DiagnosticsBundle.create(summary: summary, backendLogsDirectory: logsDirectory)
+      DispatchQueue.main.async {
+        self.operations.end(operation)
+        guard let bundleURL = bundleURL else {
+          self.showError("Unable to create the diagnostics bundle.")
+          return
+        }
+        NSPasteboard.general.clearContents()
+        NSPasteboard.general.setString(ShellLog.redact(summary) + "\n\nDiagnostics: \(bundleURL.path)", forType: .string)
+        NSWorkspace.shared.activateFileViewerSelecting([bundleURL])
+      }
+    }
+  }
+
+  private func diagnosticsSummary() -> String {
+    let health = healthState()
+    var lines = [
+      "Invest Log \(health.version)",
+      "macOS \(ProcessInfo.processInfo.operatingSystemVersionString)",
+      "Backend: \(health.backendRunning ? "running" : "stopped")" + (health.backendPID.map { " (pid \($0))" } ?? ""),
+      "Address: \(health.baseURL.absoluteString)",
+    ]
+    if let failure = startupFailure {
+      lines.append("Startup failure: \(failure.code.rawValue) at \"\(failure.stage.label)\" — \(failure.detail)")
+    }
+    lines.append("")
+    lines.append(timeline.summary)
+    return lines.joined(separator: "\n")
+  }
+
+  @objc private func runSelfTest(_ sender: Any?) {
+    guard let resourcePath = Bundle.main.resourcePath else { return }
+    SelfTest.run(resourcesURL: URL(fileURLWithPath: resourcePath), host: host, port: port) { checks in
+      let report = SelfTest.format(checks)
+      ShellLog.info("self-test finished", ["failed": checks.filter { $0.status == "fail" }.map { $0.name }.joined(separator: ",")])
+      let alert = NSAlert()
+      alert.messageText = checks.contains { $0.status == "fail" } ? "Self-test found problems" : "Self-test passed"
+      alert.informativeText = report
+      alert.addButton(withTitle: "OK")
+      alert.addButton(withTitle: "Copy")
+      if alert.runModal() == .alertSecondButtonReturn {
+        NSPasteboard.general.clearContents()
+        NSPasteboard.general.setString(report, forType: .string)
+      }
+    }
+  }
+
+  @objc private func showLogViewer(_ sender: Any?) {
+    fetchStorageInfo { [weak self] info in
+      guard let self = self else { return }
+      if let info = info {
+        self.logViewer.backendLogsDirectory = URL(fileURLWithPath: info.dataDir, isDirectory: true).appendingPathComponent("logs")
+      }
+      self.logViewer.show()
+    }
+  }
+
+  /// Same path as double-clicking a .db file: copied into the data folder if needed, then switched to.
+  @objc private func openDatabaseFromMenu(_ sender: Any?) {
+    let panel = NSOpenPanel()
+    panel.allowedContentTypes = [UTType(filenameExtension: "db") ?? .data]
+    panel.allowsMultipleSelection = false
+    panel.message = "Choose an Invest Log database to switch to."
+    guard panel.runModal() == .OK, let url = panel.url else { return }
+    openDatabaseFile(url)
+  }
+
+  /// The page builds the JSON export (Settings › Export data); the save panel comes from the download.
+  /// Only the main window's page gets the event, so one click is one export however many windows are open.
+  @objc private func exportData(_ sender: Any?) {
+    guard !requestUnlockIfLocked() else { return }
+    guard backendReady, webView.url.map(isBackendURL) == true else {
+      showError("The backend is not running.")
+      return
+    }
+    showMainWindow()
+    webView.publishShellEvent("export-data")
+  }
+
+  /// Reveals the active database file (or the data directory) in Finder.
+  @objc private func openDataFolder(_ sender: Any?) {
+    fetchStorageInfo { [weak self] info in
+      guard let info = info else {
+        self?.showError("Unable to locate the data folder. Is the backend running?")
+        return
+      }
+      let dbURL = URL(fileURLWithPath: info.dbPath)
+      if FileManager.default.fileExists(atPath: dbURL.path) {
+        NSWorkspace.shared.activateFileViewerSelecting([dbURL])
+      } else {
+        NSWorkspace.shared.open(URL(fileURLWithPath: info.dataDir, isDirectory: true))
+      }
+    }
+  }
+
+  /// Asks the backend where its data lives; the shell never resolves config itself.
+  private func fetchStorageInfo(completion: @escaping (StorageInfo?) -> Void) {
+    let url = URL(string: "\(BackendTLS.scheme)://\(host):\(port)/api/storage")!
+    var request = URLRequest(url: url)
+    request.timeoutInterval = 2.0
+
+    BackendTLS.session.dataTask(with: request) { data, response, _ in
+      var info: StorageInfo?
+      if let http = response as? HTTPURLResponse, http.statusCode == 200, let data = data {
+        info = try? JSONDecoder().decode(StorageInfo.self, from: data)
+      }
+      DispatchQueue.main.async { completion(info) }
+    }.resume()
+  }
+
+  private func showError(_ message: String) {
+    let alert = NSAlert()
+    alert.messageText = "Invest Log"
+    alert.informativeText = message
+    alert.alertStyle = .warning
+    alert.runModal()
+  }
 }
 
 let app = NSApplication.shared
@@ -1,6 +1,10 @@
+const TRANSACTION_VIEW_ROUTES = new Set(['overview', 'holdings', 'transactions', 'charts']);
+
 function init() {
   state.apiBase = resolveApiBase();
   state.privacy = localStorage.getItem('privacyMode') === '1';
+  // ?view=compact drops the chrome for the desktop wrapper's quick-add popup.
+  document.body.classList.toggle('compact', new URLSearchParams(window.location.search).get('view') === 'compact');
   document.body.classList.toggle('privacy', state.privacy);
 
   privacyToggle.addEventListener('click', () => {
@@ -16,6 +20,54 @@ function init() {
     _openPopover = null;
   });
 
+  // The macOS shell builds its own right-click menu from what was under the pointer.
+  const shellContext = window.webkit?.messageHandlers?.shellContext;
+  if (shellContext) {
+    document.addEventListener('contextmenu', (event) => {
+      // Holdings rows keep symbol/currency/account on their first action control (the
+      // row's own data-account is a sort key), so look inside the row under the pointer.
+      const row = event.target.closest?.('tr');
+      const source = row?.querySelector('[data-symbol]') || event.target.closest?.('[data-symbol]');
+      shellContext.postMessage({
+        selection: !!String(window.getSelection() || ''),
+        symbol: source?.dataset.symbol || '',
+        currency: source?.dataset.currency || '',
+        account: source?.dataset.account || '',
+      });
+    });
+  }
+
+  // Pushed by the macOS shell; the connection pill is otherwise only checked on load.
+  onShellEvent('backend', (detail) => {
+    if (detail.state === 'running') {
+      updateConnectionStatus();
+      return;
+    }
+    connectionPill.textContent = detail.state === 'restarting' ? 'Restarting' : 'Offline';
+    connectionPill.classList.remove('online');
+  });
+  // Only views that list or summarize transactions refresh; the add/transfer forms and any
+  // open prompt keep what the user has typed.
+  onShellEvent('transactions-changed', () => {
+    if (!TRANSACTION_VIEW_ROUTES.has(currentRoute() || 'overview')) return;
+    if (document.querySelector('#prompt-overlay:not(.hidden), #confirm-overlay:not(.hidden)')) return;
+    renderRoute();
+  });
+  // File › Export Data… in the shell runs the same export as the Settings button.
+  onShellEvent('export-data', async () => {
+    try {
+      showToast('Preparing export...');
+      await exportBackupData();
+      showToast('Export ready');
+    } catch (err) {
+      showToast('Export failed');
+    }
+  });
+  onShellEvent('system-wake', () => {
+    updateConnectionStatus();
+    renderRoute();
+  });
+
   window.addEventListener('hashchange', renderRoute);
   renderRoute();
   updateConnectionStatus();
@@ -108,6 +108,60 @@ function setAIAnalysisAPIKey(apiKey) {
   return normalized;
 }
 
+const shellAIAPIKeyName = 'ai_api_key';
+
+// The macOS shell keeps credentials in the Keychain; elsewhere the key stays in the backend.
+function shellCredentials() {
+  return window.webkit?.messageHandlers?.credentials || null;
+}
+
+async function readShellCredential(name) {
+  const handler = shellCredentials();
+  if (!handler) {
+    return '';
+  }
+  try {
+    return String(await handler.postMessage({ action: 'retrieve', name }) || '').trim();
+  } catch (_) {
+    return '';
+  }
+}
+
+async function writeShellCredential(name, value) {
+  const handler = shellCredentials();
+  if (!handler) {
+    return false;
+  }
+  try {
+    await handler.postMessage({ action: 'store', name, value: String(value || '').trim() });
+    return true;
+  } catch (_) {
+    return false;
+  }
+}
+
+// Moves a key still held by the backend or localStorage into the Keychain, then clears
+// those copies. Keeps them if the Keychain write fails.
+async function moveAIAnalysisAPIKeyToKeychain(settings, currentKey) {
+  const stored = await readShellCredential(shellAIAPIKeyName);
+  const apiKey = stored || currentKey;
+  if (!apiKey) {
+    return '';
+  }
+  if (!stored && !(await writeShellCredential(shellAIAPIKeyName, apiKey))) {
+    return apiKey;
+  }
+  if (currentKey) {
+    try {
+      await persistAIAnalysisSettings({ ...settings, apiKey: '' });
+    } catch (_) {
+      // The Keychain copy wins on the next load either way.
+    }
+  }
+  setAIAnalysisAPIKey('');
+  return apiKey;
+}
+
 function isDefaultAIAnalysisSettings(settings) {
   const defaults = defaultAIAnalysisSettings();
   return settings.baseUrl === defaults.baseUrl &&
@@ -160,7 +214,7 @@ async function loadAIAnalysisSettings(options = {}) {
     if (legacySettings) {
       normalized = normalizeAIAnalysisSettings(legacySettings);
     }
-    apiKey = getAIAnalysisAPIKey(legacySettings);
+    apiKey = (await readShellCredential(shellAIAPIKeyName)) || getAIAnalysisAPIKey(legacySettings);
   }
 
   if (loadedFromServer) {
@@ -191,6 +245,10 @@ async function loadAIAnalysisSettings(options = {}) {
       }
       localStorage.removeItem(aiAnalysisSettingsKey);
     }
+
+    if (shellCredentials()) {
+      apiKey = await moveAIAnalysisAPIKeyToKeychain(normalized, apiKey);
+    }
   }
 
   state.aiSettings = { ...normalized, apiKey };
@@ -199,11 +257,12 @@ async function loadAIAnalysisSettings(options = {}) {
 }
 
 async function saveAIAnalysisSettings(settings) {
-  const saved = await persistAIAnalysisSettings(settings);
   const apiKey = String(settings && settings.apiKey ? settings.apiKey : '').trim();
+  const inKeychain = await writeShellCredential(shellAIAPIKeyName, apiKey);
+  const saved = await persistAIAnalysisSettings(inKeychain ? { ...settings, apiKey: '' } : settings);
   state.aiSettings = { ...saved, apiKey };
   state.aiSettingsLoaded = true;
-  setAIAnalysisAPIKey(apiKey);  // keep localStorage in sync as fallback
+  setAIAnalysisAPIKey(inKeychain ? '' : apiKey);  // keep localStorage in sync as fallback
   localStorage.removeItem(aiAnalysisSettingsKey);
   return state.aiSettings;
 }
@@ -247,6 +247,7 @@ function bindAIAnalysisActions(selectedMethod, history) {
         await postSSE('/api/ai-analysis/stream', {
           method_id: selectedMethod.id,
           variables,
+          api_key: settings.apiKey,
         }, {
           onProgress: (payload) => {
             state.aiAnalysisStreaming.stage = payload && payload.message
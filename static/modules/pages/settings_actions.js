@@ -391,6 +391,8 @@ function bindSettingsActions(assetTypes, aiAnalysisMethods = []) {
         } else {
           showToast(`Fetched ${updated} rate(s)`);
         }
+        // The user just chose to fetch rates online; catch up on daily history alongside.
+        fetchJSON('/api/exchange-rates/backfill', { method: 'POST', body: JSON.stringify({}) }).catch(() => {});
         renderSettings();
       } catch (err) {
         showToast('Fetch exchange rates failed');
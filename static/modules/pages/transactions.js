@@ -12,6 +12,8 @@ async function renderTransactions() {
     const filterAccount = (query.get('account') || '').trim();
     const filterStartDate = (query.get('start_date') || '').trim();
     const filterEndDate = (query.get('end_date') || '').trim();
+    // Set by Spotlight results in the macOS shell: the row to bring into view.
+    const focusId = (query.get('id') || '').trim();
     const pageSize = 100;
     const pageRaw = Number.parseInt(query.get('page') || '1', 10);
     const page = Number.isNaN(pageRaw) || pageRaw < 1 ? 1 : pageRaw;
@@ -123,7 +125,7 @@ async function renderTransactions() {
       const resolvedAccountName = t.account_name || accountNameMap.get(t.account_id) || t.account_id || '';
       const showAccountSub = Boolean(t.account_name || accountNameMap.get(t.account_id));
       return `
-        <tr>
+        <tr data-transaction-id="${t.id}">
           <td>${escapeHtml(t.transaction_date)}</td>
           <td><strong>${escapeHtml(displayName)}</strong>${showSymbolSub ? `<br><span class="section-sub">${escapeHtml(t.symbol)}</span>` : ''}</td>
           <td><span class="tag ${tagClass}">${escapeHtml(t.transaction_type)}</span></td>
@@ -232,6 +234,12 @@ async function renderTransactions() {
       ${totalPages > 1 ? pagination : ''}
     `;
 
+    const focusRow = focusId ? view.querySelector(`tr[data-transaction-id="${CSS.escape(focusId)}"]`) : null;
+    if (focusRow) {
+      focusRow.classList.add('highlighted');
+      focusRow.scrollIntoView({ block: 'center' });
+    }
+
     const filterForm = view.querySelector('#tx-filter');
     if (filterForm) {
       filterForm.addEventListener('submit', (event) => {
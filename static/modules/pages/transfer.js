@@ -360,6 +360,7 @@ async function exportBackupData() {
   document.body.appendChild(link);
   link.click();
   link.remove();
-  URL.revokeObjectURL(url);
+  // The desktop shell turns the click into a download asynchronously; keep the blob alive until then.
+  setTimeout(() => URL.revokeObjectURL(url), 10000);
 }
 
@@ -15,9 +15,13 @@ function getRouteQuery() {
 }
 
 
-function renderRoute() {
+function currentRoute() {
   const hash = window.location.hash || '#/overview';
-  const route = hash.replace('#/', '').split('?')[0];
+  return hash.replace('#/', '').split('?')[0];
+}
+
+function renderRoute() {
+  const route = currentRoute();
   switch (route) {
     case 'holdings':
       setActiveRoute('holdings');
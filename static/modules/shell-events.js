@@ -0,0 +1,22 @@
+// Events pushed by the macOS shell (publishShellEvent in macos/main.swift) instead of the
+// page polling for them. Each arrives as an `investlog:shell` window event carrying
+// `{ type, detail }`; outside the shell none are ever fired.
+const shellEventHandlers = {};
+
+function onShellEvent(type, handler) {
+  if (!shellEventHandlers[type]) {
+    shellEventHandlers[type] = [];
+  }
+  shellEventHandlers[type].push(handler);
+}
+
+window.addEventListener('investlog:shell', (event) => {
+  const { type, detail } = event.detail || {};
+  (shellEventHandlers[type] || []).forEach((handler) => {
+    try {
+      handler(detail || {});
+    } catch (err) {
+      console.error(`shell event ${type} handler failed`, err);
+    }
+  });
+});
@@ -2,7 +2,7 @@
  * Service Worker for Invest Log SPA
  */
 
-const CACHE_NAME = 'invest-log-v6';
+const CACHE_NAME = 'invest-log-v13';
 const STATIC_ASSETS = [
   './',
   './index.html',
@@ -12,6 +12,7 @@ const STATIC_ASSETS = [
   './modules/utils.js',
   './modules/api.js',
   './modules/ui.js',
+  './modules/shell-events.js',
   './modules/charts.js',
   './modules/ai-settings.js',
   './modules/ai-analysis-methods.js',